@@ -0,0 +1,483 @@
+//! Derive macro for [`hyperopt`][1] search spaces.
+//!
+//! Normally, one uses it through the `derive` feature of `hyperopt`:
+//!
+//! ```rust
+//! use fastrand::Rng;
+//! use hyperopt::SearchSpace;
+//!
+//! #[derive(Copy, Clone, Debug, PartialEq)]
+//! enum Activation {
+//!     Relu,
+//!     Tanh,
+//! }
+//!
+//! #[derive(SearchSpace)]
+//! struct TrainConfig {
+//!     #[hyperopt(range = 1e-5..=1e-1, log)]
+//!     lr: f64,
+//!
+//!     #[hyperopt(range = 1..=16, kernel = Binomial)]
+//!     depth: u32,
+//!
+//!     #[hyperopt(choices = [Activation::Relu, Activation::Tanh])]
+//!     activation: Activation,
+//! }
+//!
+//! let mut optimizer = TrainConfig::optimizer(Rng::with_seed(42));
+//! for _ in 0..10 {
+//!     let config = optimizer.new_trial();
+//!     assert!((1e-5..=1e-1).contains(&config.lr));
+//!     assert!((1..=16).contains(&config.depth));
+//!     let metric = i64::from(config.depth);
+//!     optimizer.feed_back(config, metric);
+//! }
+//! assert!(optimizer.best_trial().is_some());
+//! ```
+//!
+//! # Field attributes
+//!
+//! - `range = start..=end`: search range of a numeric field, required for numbers
+//! - `log`: search a floating-point field in the logarithmic scale
+//! - `q = step`: snap a numeric field to multiples of the step, like `quniform` and `qloguniform`
+//! - `kernel = Kernel`: trial kernel, either a name from `hyperopt::kernel` or a full type
+//! - `choices = [a, b, …]`: finite set of unordered values for any [`PartialEq`] field
+//!
+//! Unless specified, the kernel is `Epanechnikov` truncated to the range for floats,
+//! `Binomial` for integers, and `Categorical` for choices, so that the choices
+//! are not treated as ordered. The bare kernel names are not truncated.
+//!
+//! [1]: https://docs.rs/hyperopt
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    parse_macro_input,
+    spanned::Spanned,
+    Data,
+    DeriveInput,
+    Expr,
+    ExprArray,
+    Field,
+    Fields,
+    Type,
+};
+
+/// Derive `hyperopt::SearchSpace` and the companion `{Name}Optimizer` for a struct.
+///
+/// See the crate documentation for the supported attributes.
+#[proc_macro_derive(SearchSpace, attributes(hyperopt))]
+pub fn derive_search_space(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "`SearchSpace` can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &data.fields,
+            "`SearchSpace` requires named fields",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`SearchSpace` cannot be derived for generic structs",
+        ));
+    }
+
+    let parameters = fields
+        .named
+        .iter()
+        .map(Parameter::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let optimizer_name = format_ident!("{name}Optimizer");
+    let optimizer_doc = format!("Optimizer of the [`{name}`] search space.");
+
+    let field_names: Vec<_> = parameters.iter().map(|parameter| &parameter.name).collect();
+    let optimizer_types = parameters.iter().map(Parameter::optimizer_type);
+    let constructors = parameters.iter().map(Parameter::construct_optimizer);
+    let new_trials = parameters.iter().map(Parameter::new_trial);
+    let feed_backs = parameters.iter().map(Parameter::feed_back);
+
+    Ok(quote! {
+        #[doc = #optimizer_doc]
+        #vis struct #optimizer_name<M> {
+            #(#field_names: #optimizer_types,)*
+            best_trial: ::core::option::Option<(#name, M)>,
+        }
+
+        impl<M> #optimizer_name<M> {
+            /// Generate a new trial.
+            #vis fn new_trial(&mut self) -> #name {
+                #name {
+                    #(#field_names: #new_trials,)*
+                }
+            }
+
+            /// Feed the metric back for every parameter of the trial.
+            #vis fn feed_back(&mut self, trial: #name, metric: M)
+            where
+                M: ::core::clone::Clone + ::core::fmt::Debug + ::hyperopt::ops::TotalOrd,
+            {
+                #(#feed_backs)*
+                if self.best_trial.as_ref().map_or(true, |(_, best_metric)| {
+                    ::hyperopt::ops::TotalOrd::total_order(&metric, best_metric).is_lt()
+                })
+                {
+                    self.best_trial = ::core::option::Option::Some((trial, metric));
+                }
+            }
+
+            /// Get the best trial and its metric.
+            #vis fn best_trial(&self) -> ::core::option::Option<(&#name, &M)> {
+                self.best_trial.as_ref().map(|(trial, metric)| (trial, metric))
+            }
+        }
+
+        impl ::hyperopt::SearchSpace for #name {
+            type Optimizer<M> = #optimizer_name<M>;
+
+            fn optimizer<M>(mut rng: ::hyperopt::__private::Rng) -> Self::Optimizer<M> {
+                #optimizer_name {
+                    #(#field_names: #constructors,)*
+                    best_trial: ::core::option::Option::None,
+                }
+            }
+        }
+    })
+}
+
+/// Parsed field of the search space.
+struct Parameter {
+    name: Ident,
+    ty: Type,
+    domain: Domain,
+    kernel: Option<Type>,
+
+    /// Quantization step.
+    step: Option<Expr>,
+}
+
+enum Domain {
+    /// Floating-point number, optionally in the logarithmic scale.
+    Float { range: Expr, log: bool },
+
+    /// Integer number.
+    Integer { range: Expr },
+
+    /// Finite set of values, represented by their indices.
+    Choices(ExprArray),
+}
+
+/// Continuous kernels, generic over the parameter only.
+const CONTINUOUS_KERNELS: &[&str] = &[
+    "Biweight",
+    "Cauchy",
+    "Cosine",
+    "Epanechnikov",
+    "Gaussian",
+    "Laplace",
+    "Logistic",
+    "Triangular",
+    "Triweight",
+];
+
+/// Discrete kernels, generic over both parameter and density.
+const DISCRETE_KERNELS: &[&str] = &[
+    "BetaBinomial",
+    "Binomial",
+    "Categorical",
+    "DiscreteGaussian",
+    "Geometric",
+    "UniformNeighbourhood",
+];
+
+/// Universal kernels, generic over both parameter and density.
+const UNIVERSAL_KERNELS: &[&str] = &["Uniform"];
+
+impl Parameter {
+    fn parse(field: &Field) -> syn::Result<Self> {
+        let name = field.ident.clone().expect("fields should be named");
+
+        let mut range = None;
+        let mut log = false;
+        let mut kernel = None;
+        let mut step = None;
+        let mut choices = None;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("hyperopt"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("range") {
+                    range = Some(meta.value()?.parse::<Expr>()?);
+                } else if meta.path.is_ident("log") {
+                    log = true;
+                } else if meta.path.is_ident("kernel") {
+                    kernel = Some(meta.value()?.parse::<Type>()?);
+                } else if meta.path.is_ident("q") {
+                    step = Some(meta.value()?.parse::<Expr>()?);
+                } else if meta.path.is_ident("choices") {
+                    choices = Some(meta.value()?.parse::<ExprArray>()?);
+                } else {
+                    return Err(meta.error("unsupported `hyperopt` attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        let domain = match (range, choices) {
+            (Some(_), Some(choices)) => {
+                return Err(syn::Error::new_spanned(
+                    choices,
+                    "`range` and `choices` are mutually exclusive",
+                ));
+            }
+            (None, Some(choices)) if choices.elems.is_empty() => {
+                return Err(syn::Error::new_spanned(
+                    choices,
+                    "`choices` must not be empty",
+                ));
+            }
+            (None, Some(choices)) => Domain::Choices(choices),
+            (Some(range), None) if is_float(&field.ty) => Domain::Float { range, log },
+            (Some(range), None) if is_integer(&field.ty) => Domain::Integer { range },
+            (Some(_), None) => {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    "`range` requires one of `f64`, `i8`, `i16`, `i32`, `u8`, `u16` or `u32`",
+                ));
+            }
+            (None, None) => {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "every field requires either `#[hyperopt(range = …)]` or `#[hyperopt(choices = […])]`",
+                ));
+            }
+        };
+        if log && !matches!(domain, Domain::Float { .. }) {
+            return Err(syn::Error::new(
+                field.span(),
+                "`log` is only supported for floating-point fields",
+            ));
+        }
+
+        if let (Some(step), Domain::Choices(_)) = (&step, &domain) {
+            return Err(syn::Error::new_spanned(
+                step,
+                "`q` is only supported for numeric fields",
+            ));
+        }
+
+        Ok(Self {
+            name,
+            ty: field.ty.clone(),
+            domain,
+            kernel,
+            step,
+        })
+    }
+
+    /// Type of the optimized parameter.
+    fn param_type(&self) -> TokenStream2 {
+        let ty = &self.ty;
+        match self.domain {
+            Domain::Float { .. } | Domain::Integer { .. } => quote! { #ty },
+            Domain::Choices(_) => quote! { i32 },
+        }
+    }
+
+    /// Type of the densities.
+    fn density_type(&self) -> TokenStream2 {
+        match self.domain {
+            Domain::Float { .. } => self.param_type(),
+            Domain::Integer { .. } | Domain::Choices(_) => quote! { f64 },
+        }
+    }
+
+    fn init_kernel_type(&self) -> TokenStream2 {
+        let param = self.param_type();
+        let density = self.density_type();
+        quote! { ::hyperopt::kernel::universal::Uniform<#param, #density> }
+    }
+
+    fn kernel_type(&self) -> TokenStream2 {
+        let param = self.param_type();
+        let density = self.density_type();
+        let Some(kernel) = &self.kernel else {
+            return match self.domain {
+                Domain::Float { .. } => quote! {
+                    ::hyperopt::kernel::truncated::Truncated<
+                        ::hyperopt::kernel::continuous::Epanechnikov<#param>
+                    >
+                },
+                Domain::Integer { .. } => {
+                    quote! { ::hyperopt::kernel::discrete::Binomial<#param, #density> }
+                }
+                // The choice indices have no order, so that the kernel must not favour the neighbours:
+                Domain::Choices(_) => {
+                    quote! { ::hyperopt::kernel::discrete::Categorical<#param, #density> }
+                }
+            };
+        };
+
+        // Bare kernel names are looked up in `hyperopt::kernel`, anything else is used as is:
+        let Type::Path(path) = kernel else {
+            return kernel.to_token_stream();
+        };
+        let Some(ident) = path.path.get_ident() else {
+            return kernel.to_token_stream();
+        };
+        let name = ident.to_string();
+        if CONTINUOUS_KERNELS.contains(&name.as_str()) {
+            quote_spanned! { ident.span()=> ::hyperopt::kernel::continuous::#ident<#param> }
+        } else if DISCRETE_KERNELS.contains(&name.as_str()) {
+            quote_spanned! { ident.span()=> ::hyperopt::kernel::discrete::#ident<#param, #density> }
+        } else if UNIVERSAL_KERNELS.contains(&name.as_str()) {
+            quote_spanned! { ident.span()=> ::hyperopt::kernel::universal::#ident<#param, #density> }
+        } else {
+            kernel.to_token_stream()
+        }
+    }
+
+    fn optimizer_type(&self) -> TokenStream2 {
+        let init_kernel = self.init_kernel_type();
+        let param = self.param_type();
+        let kernel = self.kernel_type();
+        quote! { ::hyperopt::Optimizer<#init_kernel, #param, M, #kernel> }
+    }
+
+    /// Expression that evaluates to the search range in terms of the optimized parameter.
+    fn range(&self) -> TokenStream2 {
+        let ty = &self.ty;
+        match &self.domain {
+            Domain::Float { range, log } => {
+                let transform = if *log {
+                    quote! { (::hyperopt::__private::ln(start), ::hyperopt::__private::ln(end)) }
+                } else {
+                    quote! { (start, end) }
+                };
+                quote! {{
+                    let range: ::core::ops::RangeInclusive<#ty> = #range;
+                    let (start, end) = range.into_inner();
+                    let (start, end) = #transform;
+                    start..=end
+                }}
+            }
+            Domain::Integer { range } => quote! {{
+                let range: ::core::ops::RangeInclusive<#ty> = #range;
+                range
+            }},
+            Domain::Choices(choices) => {
+                let n_choices = i32::try_from(choices.elems.len()).expect("too many choices");
+                let max_index = n_choices - 1;
+                quote! { 0..=#max_index }
+            }
+        }
+    }
+
+    fn construct_optimizer(&self) -> TokenStream2 {
+        let range = self.range();
+        let quantization = self.step.as_ref().map(|step| {
+            let quantization = match self.domain {
+                Domain::Float { log: true, .. } => {
+                    quote! { ::hyperopt::quantization::Logarithmic::new(#step) }
+                }
+                Domain::Float { log: false, .. } | Domain::Integer { .. } => {
+                    quote! { ::hyperopt::quantization::Linear::new(#step) }
+                }
+                Domain::Choices(_) => unreachable!("choices cannot be quantized"),
+            };
+            quote! { .quantization(#quantization) }
+        });
+        quote! {{
+            let range = #range;
+            ::hyperopt::Optimizer::builder(
+                range.clone(),
+                ::hyperopt::kernel::universal::Uniform::with_bounds(range),
+                rng.fork(),
+            )
+            .kernel()
+            .allow_repeats(true)
+            #quantization
+            .build()
+        }}
+    }
+
+    fn new_trial(&self) -> TokenStream2 {
+        let name = &self.name;
+        let parameter = quote! { self.#name.new_trial() };
+        match &self.domain {
+            Domain::Float { log: false, .. } | Domain::Integer { .. } => parameter,
+            Domain::Float { range, log: true } => {
+                let ty = &self.ty;
+                // Snap once again to compensate for rounding errors of the `ln` – `exp` round trip:
+                let snap = self.step.as_ref().map(|step| {
+                    quote! { let value = ::hyperopt::__private::round(value / #step) * #step; }
+                });
+                quote! {{
+                    let range: ::core::ops::RangeInclusive<#ty> = #range;
+                    let value = ::hyperopt::__private::exp(#parameter);
+                    #snap
+                    value.clamp(*range.start(), *range.end())
+                }}
+            }
+            Domain::Choices(choices) => {
+                let indices = 0..i32::try_from(choices.elems.len()).expect("too many choices");
+                let values = choices.elems.iter();
+                quote! {
+                    match #parameter {
+                        #(#indices => #values,)*
+                        _ => unreachable!("the index should be within the range"),
+                    }
+                }
+            }
+        }
+    }
+
+    fn feed_back(&self) -> TokenStream2 {
+        let name = &self.name;
+        let parameter = match &self.domain {
+            Domain::Float { log: true, .. } => quote! { ::hyperopt::__private::ln(trial.#name) },
+            Domain::Float { log: false, .. } | Domain::Integer { .. } => quote! { trial.#name },
+            Domain::Choices(choices) => {
+                let values = choices.elems.iter();
+                quote! {{
+                    let index = [#(#values),*]
+                        .iter()
+                        .position(|choice| *choice == trial.#name)
+                        .expect("the value should be one of the choices");
+                    i32::try_from(index).expect("the index should fit into `i32`")
+                }}
+            }
+        };
+        quote! { self.#name.feed_back(#parameter, metric.clone()); }
+    }
+}
+
+fn is_float(ty: &Type) -> bool {
+    is_one_of(ty, &["f64"])
+}
+
+/// Integer types that can be converted into the `f64`-based densities.
+fn is_integer(ty: &Type) -> bool {
+    is_one_of(ty, &["i8", "i16", "i32", "u8", "u16", "u32"])
+}
+
+fn is_one_of(ty: &Type, names: &[&str]) -> bool {
+    matches!(ty, Type::Path(path) if path.path.get_ident().is_some_and(|ident| names.contains(&ident.to_string().as_str())))
+}
@@ -0,0 +1,88 @@
+use fastrand::Rng;
+use hyperopt::SearchSpace;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Activation {
+    Relu,
+    Tanh,
+    Gelu,
+}
+
+#[derive(Debug, SearchSpace)]
+struct TrainConfig {
+    #[hyperopt(range = 1e-5..=1e-1, log)]
+    lr: f64,
+
+    #[hyperopt(range = 0.0..=0.5, kernel = Epanechnikov)]
+    dropout: f64,
+
+    #[hyperopt(range = 1..=16, kernel = Binomial)]
+    depth: u32,
+
+    #[hyperopt(choices = [Activation::Relu, Activation::Tanh, Activation::Gelu])]
+    activation: Activation,
+}
+
+#[derive(Debug, SearchSpace)]
+struct QuantizedConfig {
+    #[hyperopt(range = 0.0..=0.5, q = 0.05)]
+    dropout: f64,
+
+    #[hyperopt(range = 0.5..=100.0, q = 0.5, log)]
+    weight_decay: f64,
+
+    #[hyperopt(range = 32..=512, q = 32)]
+    batch_size: u32,
+}
+
+#[test]
+fn new_trial_within_range_ok() {
+    let mut optimizer = TrainConfig::optimizer::<i64>(Rng::with_seed(42));
+    for i in 0..30 {
+        let config = optimizer.new_trial();
+        assert!((1e-5..=1e-1).contains(&config.lr), "{config:?}");
+        assert!((0.0..=0.5).contains(&config.dropout), "{config:?}");
+        assert!((1..=16).contains(&config.depth), "{config:?}");
+        optimizer.feed_back(config, i);
+    }
+}
+
+#[test]
+fn best_trial_ok() {
+    let mut optimizer = TrainConfig::optimizer(Rng::with_seed(42));
+    let mut min_metric = i64::MAX;
+    for _ in 0..50 {
+        let config = optimizer.new_trial();
+        let metric = i64::from(config.depth) + i64::from(config.activation != Activation::Gelu);
+        min_metric = min_metric.min(metric);
+        optimizer.feed_back(config, metric);
+    }
+    let (best_config, best_metric) = optimizer.best_trial().unwrap();
+    assert_eq!(*best_metric, min_metric);
+    assert_eq!(
+        i64::from(best_config.depth) + i64::from(best_config.activation != Activation::Gelu),
+        min_metric,
+    );
+}
+
+#[test]
+#[allow(clippy::cast_possible_truncation, clippy::float_cmp)]
+fn quantized_ok() {
+    let mut optimizer = QuantizedConfig::optimizer(Rng::with_seed(42));
+    for i in 0..30 {
+        let config = optimizer.new_trial();
+        assert_eq!(
+            (config.dropout / 0.05).round() * 0.05,
+            config.dropout,
+            "{config:?}"
+        );
+        assert_eq!(
+            (config.weight_decay / 0.5).round() * 0.5,
+            config.weight_decay,
+            "{config:?}",
+        );
+        assert!((0.5..=100.0).contains(&config.weight_decay), "{config:?}");
+        assert_eq!(config.batch_size % 32, 0, "{config:?}");
+        optimizer.feed_back(config, i);
+    }
+}
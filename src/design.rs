@@ -0,0 +1,23 @@
+//! Quasi-random [initial designs][1] for the first trials of [`crate::Optimizer`].
+//!
+//! Pseudo-random samples tend to clump for small budgets, leaving parts of the search range
+//! unexplored. Low-discrepancy sequences cover the range much more evenly,
+//! so the startup trials tell the optimizer more about the target function.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Low-discrepancy_sequence
+
+use core::fmt::Debug;
+
+mod halton;
+mod latin_hypercube;
+mod sobol;
+
+pub use self::{halton::Halton, latin_hypercube::LatinHypercube, sobol::Sobol};
+
+/// Initial design: a sequence of points within the unit interval `[0, 1)`.
+///
+/// Any debuggable iterator of [`f64`] is a design, so the standard adapters work as usual:
+/// for example, `Halton::default().take(10)` limits the design to the first 10 trials.
+pub trait Design: Iterator<Item = f64> + Debug {}
+
+impl<T> Design for T where T: Iterator<Item = f64> + Debug {}
@@ -0,0 +1,74 @@
+/// One-dimensional [Halton sequence][1], also known as the van der Corput sequence.
+///
+/// The sequence skips the leading zero, which is the very bound of the search range,
+/// and starts at `1 / base`.
+///
+/// [1]: https://en.wikipedia.org/wiki/Halton_sequence
+#[derive(Clone, Debug)]
+pub struct Halton {
+    base: u64,
+    index: u64,
+}
+
+impl Halton {
+    /// Construct the sequence with the given base.
+    ///
+    /// # Panics
+    ///
+    /// The base must be at least `2`.
+    #[must_use]
+    pub fn new(base: u64) -> Self {
+        assert!(base >= 2, "the base must be at least `2`, got `{base}`");
+        Self { base, index: 0 }
+    }
+}
+
+impl Default for Halton {
+    /// Base-2 sequence.
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl Iterator for Halton {
+    type Item = f64;
+
+    /// Calculate the [radical inverse][1] of the next index.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Van_der_Corput_sequence
+    #[allow(clippy::cast_precision_loss)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.index = self.index.checked_add(1)?;
+
+        let mut index = self.index;
+        let mut fraction = 1.0;
+        let mut point = 0.0;
+        while index != 0 {
+            fraction /= self.base as f64;
+            point += fraction * (index % self.base) as f64;
+            index /= self.base;
+        }
+        Some(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn base_2_ok() {
+        assert_eq!(
+            Halton::default().take(7).collect::<Vec<_>>(),
+            [0.5, 0.25, 0.75, 0.125, 0.625, 0.375, 0.875]
+        );
+    }
+
+    #[test]
+    fn base_3_ok() {
+        let points = Halton::new(3).take(4).collect::<Vec<_>>();
+        assert_eq!(points, [1.0 / 3.0, 2.0 / 3.0, 1.0 / 9.0, 4.0 / 9.0]);
+    }
+}
@@ -0,0 +1,60 @@
+use alloc::vec::{IntoIter, Vec};
+
+use fastrand::Rng;
+
+/// [Latin hypercube][1] design of a fixed size.
+///
+/// The unit interval is split into `n` equal strata, and each stratum gets exactly one
+/// uniformly distributed point. Only the whole design is stratified: the strata are visited
+/// in random order, so a prefix of it is no better spread than the random points.
+/// For a budget, which may be cut short, prefer the low-discrepancy [`super::Sobol`]
+/// or [`super::Halton`] sequences.
+///
+/// [1]: https://en.wikipedia.org/wiki/Latin_hypercube_sampling
+#[derive(Clone, Debug)]
+pub struct LatinHypercube(IntoIter<f64>);
+
+impl LatinHypercube {
+    /// Generate the design of `n` points.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(n: usize, rng: &mut Rng) -> Self {
+        let mut strata: Vec<usize> = (0..n).collect();
+        rng.shuffle(&mut strata);
+        let points: Vec<f64> = strata
+            .into_iter()
+            .map(|stratum| (stratum as f64 + rng.f64()) / n as f64)
+            .collect();
+        Self(points.into_iter())
+    }
+}
+
+impl Iterator for LatinHypercube {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for LatinHypercube {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn one_point_per_stratum_ok() {
+        let mut rng = Rng::with_seed(42);
+        let mut strata: Vec<usize> = LatinHypercube::new(10, &mut rng)
+            .map(|point| (point * 10.0) as usize)
+            .collect();
+        strata.sort_unstable();
+        assert_eq!(strata, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,55 @@
+/// One-dimensional [Sobol sequence][1].
+///
+/// In one dimension, it is the base-2 van der Corput sequence in the Gray code order.
+/// Just like [`super::Halton`], the sequence skips the leading zero, which is the very bound
+/// of the search range, and starts at `½`. It ends after `2³² - 1` points.
+///
+/// [1]: https://en.wikipedia.org/wiki/Sobol_sequence
+#[derive(Clone, Debug)]
+pub struct Sobol {
+    index: u64,
+    state: u32,
+}
+
+impl Default for Sobol {
+    fn default() -> Self {
+        // Skip the leading zero, which flips the highest direction bit:
+        Self {
+            index: 1,
+            state: 1 << 31,
+        }
+    }
+}
+
+impl Iterator for Sobol {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index > u64::from(u32::MAX) {
+            return None;
+        }
+        let point = f64::from(self.state) / 4_294_967_296.0;
+
+        // Flip the direction bit, corresponding to the lowest zero bit of the index:
+        let direction = 1 << (31 - self.index.trailing_ones().min(31));
+        self.state ^= direction;
+        self.index += 1;
+
+        Some(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn sobol_ok() {
+        assert_eq!(
+            Sobol::default().take(8).collect::<Vec<_>>(),
+            [0.5, 0.75, 0.25, 0.375, 0.875, 0.625, 0.125, 0.1875]
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Errors of the fallible API.
+
+use core::fmt::{Display, Formatter};
+
+/// Error of the [`crate::Optimizer`] and the [kernels][crate::kernel::Kernel].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// NaN parameter was fed back.
+    NanParameter,
+
+    /// The parameter has already been tried, while [repeats][crate::OptimizerBuilder::allow_repeats]
+    /// are not allowed.
+    RepeatedParameter,
+
+    /// The trial is not pending: it has never been started, or it has been finished already.
+    UnknownTrial { id: u64 },
+
+    /// A number cannot be converted to the parameter or density type.
+    ConversionFailed,
+
+    /// The kernel bandwidth is not positive, or the kernel bounds are empty.
+    InvalidBandwidth,
+
+    /// The «bad» density is not positive at a candidate.
+    NonPositiveDensity,
+
+    /// No candidates were evaluated.
+    NoCandidates,
+
+    /// Every sample has already been tried: the search space is exhausted.
+    Exhausted,
+
+    /// The search range is empty, or has zero width while continuous or periodic.
+    InvalidRange,
+
+    /// The ratio of «good» trials is not within `(0, 1]`.
+    InvalidCutoff,
+
+    /// The quantization step is not positive, or not finite.
+    InvalidStep,
+
+    /// There are no sample points to estimate the density from.
+    NoSamples,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NanParameter => write!(f, "the parameter is NaN"),
+            Self::RepeatedParameter => write!(f, "the parameter has already been tried"),
+            Self::UnknownTrial { id } => write!(f, "trial #{id} is not pending"),
+            Self::ConversionFailed => write!(f, "the number cannot be converted"),
+            Self::InvalidBandwidth => write!(f, "the kernel bandwidth should be positive"),
+            Self::NonPositiveDensity => write!(f, "the «bad» density should be positive"),
+            Self::NoCandidates => write!(f, "there should be at least one candidate"),
+            Self::Exhausted => write!(f, "the search space is exhausted"),
+            Self::InvalidRange => write!(f, "the search range should not be empty"),
+            Self::InvalidCutoff => write!(f, "the cutoff should be within `(0, 1]`"),
+            Self::InvalidStep => write!(f, "the quantization step should be positive and finite"),
+            Self::NoSamples => write!(f, "there should be at least one sample point"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
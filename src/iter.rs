@@ -1,6 +1,8 @@
-use std::{fmt::Debug, iter};
+use core::{fmt::Debug, iter};
 
-use crate::traits::ops::Additive;
+use num_traits::{CheckedAdd, One, Zero};
+
+use crate::traits::ops::{Additive, Exp, Ln, Multiplicative};
 
 /// Iterator over 3-tuple windows, including partial ones.
 ///
@@ -47,28 +49,41 @@ pub enum Triple<T> {
     Middle(T),
 }
 
+/// Iterate from the start with the step, until the next item would overflow.
 pub fn range_step_from<T>(start: T, step: T) -> impl Iterator<Item = T>
 where
-    T: Copy + Additive,
+    T: Copy + CheckedAdd,
 {
-    let mut item = start;
-    iter::from_fn(move || {
-        let yield_ = Some(item);
-        item = item + step;
-        yield_
-    })
+    iter::successors(Some(start), move |item| item.checked_add(&step))
 }
 
-pub fn range_inclusive<T>(start: T, end: T) -> impl Iterator<Item = T>
+/// Calculate `ln(Σ exp(xᵢ))` in a numerically stable way and in a single pass.
+///
+/// Empty sum yields `ln(0) = -∞`.
+///
+/// See also: [LogSumExp](https://en.wikipedia.org/wiki/LogSumExp).
+pub fn log_sum_exp<T>(items: impl IntoIterator<Item = T>) -> T
 where
-    T: Copy + Additive + num_traits::One + PartialOrd,
+    T: Copy + Additive + Multiplicative + PartialOrd + Exp + Ln + Zero + One,
 {
-    range_step_from(start, T::one()).take_while(move |item| *item <= end)
+    let neg_infinity = T::zero().ln();
+    let max_and_sum = items
+        .into_iter()
+        // Zero terms do not contribute, and `-∞ - -∞` would be undefined:
+        .filter(|item| *item > neg_infinity)
+        .fold(None, |acc, item| match acc {
+            None => Some((item, T::one())),
+            // Rescale the sum to the new maximum:
+            Some((max, sum)) if item > max => Some((item, sum * (max - item).exp() + T::one())),
+            Some((max, sum)) => Some((max, sum + (item - max).exp())),
+        });
+    max_and_sum.map_or(neg_infinity, |(max, sum)| max + sum.ln())
 }
 
 #[cfg(test)]
 mod tests {
-    use std::iter;
+    use alloc::vec::Vec;
+    use core::iter;
 
     use super::*;
     use crate::iter::Triple::*;
@@ -79,11 +94,19 @@ mod tests {
             range_step_from(1, 2).take(5).collect::<Vec<_>>(),
             [1, 3, 5, 7, 9]
         );
+        assert_eq!(
+            range_step_from(250_u8, 2).collect::<Vec<_>>(),
+            [250, 252, 254]
+        );
     }
 
     #[test]
-    fn range_inclusive_ok() {
-        assert_eq!(range_inclusive(1, 5).collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+    #[allow(clippy::float_cmp)]
+    fn log_sum_exp_ok() {
+        approx::assert_abs_diff_eq!(log_sum_exp([1.0_f64.ln(), 2.0_f64.ln()]), 3.0_f64.ln());
+        approx::assert_abs_diff_eq!(log_sum_exp([-1000.0, -1000.0]), 2.0_f64.ln() - 1000.0);
+        assert_eq!(log_sum_exp([f64::NEG_INFINITY, 0.0]), 0.0);
+        assert_eq!(log_sum_exp::<f64>([]), f64::NEG_INFINITY);
     }
 
     #[test]
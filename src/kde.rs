@@ -1,11 +1,25 @@
 //! Kernel density estimator implementation.
 
-use std::fmt::Debug;
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt::Debug,
+    ops::{Range, RangeInclusive},
+};
 
 use fastrand::Rng;
-use num_traits::{FromPrimitive, Zero};
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
 
-use crate::{traits::ops::Arithmetic, Density, Sample};
+use crate::{
+    iter::log_sum_exp,
+    kernel::Kernel,
+    traits::ops::{Additive, Arithmetic, Exp, Ln, Rem},
+    Cdf,
+    Density,
+    Error,
+    Sample,
+};
 
 /// [Kernel density estimator][1].
 ///
@@ -14,19 +28,243 @@ use crate::{traits::ops::Arithmetic, Density, Sample};
 /// # Type parameters
 ///
 /// - [`C`]: iterator of KDE's components that are [`Density`] and [`Sample`].
+/// - [`P`]: parameter type of the components
 ///
 /// [1]: https://en.wikipedia.org/wiki/Kernel_density_estimation
 #[derive(Copy, Clone, Debug)]
-pub struct KernelDensityEstimator<C>(pub C);
+pub struct KernelDensityEstimator<C, P> {
+    components: C,
 
-impl<Ks> Density for KernelDensityEstimator<Ks>
+    /// Inclusive bounds to reflect the components across.
+    reflection: Option<(P, P)>,
+}
+
+impl<C, P> KernelDensityEstimator<C, P> {
+    /// Construct the estimator from its components.
+    pub const fn new(components: C) -> Self {
+        Self {
+            components,
+            reflection: None,
+        }
+    }
+
+    /// Apply the [reflection][1] boundary correction.
+    ///
+    /// Each component is mirrored across both bounds, repeatedly, so that the probability mass,
+    /// which would otherwise leak out of the range, is returned back to it. This removes the underestimation
+    /// of the density near the bounds. The density outside the bounds is then zero,
+    /// and the out-of-range samples are reflected back into the range.
+    ///
+    /// The correction is meant for continuous parameters: for discrete ones, the bounds
+    /// themselves get double-counted. It is also meant for light-tailed kernels: the mirror images
+    /// of heavy-tailed ones, like [`crate::kernel::continuous::Cauchy`], are summed up
+    /// over a bounded number of periods, so a small part of their mass gets lost.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Kernel_density_estimation#Bandwidth_selection
+    #[must_use]
+    pub fn reflection(mut self, bounds: RangeInclusive<P>) -> Self {
+        self.reflection = Some(bounds.into_inner());
+        self
+    }
+
+    /// Sample a random point from the KDE.
+    ///
+    /// The algorithm uses «[reservoir sampling][1]» to pick a random component,
+    /// and then samples a point from that component.
+    ///
+    /// The method returns [`None`], if the estimator has no components.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Reservoir_sampling
+    pub fn try_sample(&self, rng: &mut Rng) -> Option<P>
+    where
+        C: Iterator + Clone,
+        C::Item: Sample<Param = P>,
+        P: Copy + Additive + Rem + PartialOrd + Zero,
+    {
+        let sample = self
+            .components
+            .clone()
+            .enumerate()
+            .filter(|(i, _)| rng.usize(0..=*i) == 0)
+            .last()?
+            .1
+            .sample(rng);
+        let sample = match self.reflection {
+            Some((min, max)) if sample < min || sample > max => fold(sample, min, max),
+            _ => sample,
+        };
+        Some(sample)
+    }
+}
+
+/// Reflect the point across the bounds, until it gets into the range.
+///
+/// The repeated reflections are periodic with the period of the double range width,
+/// so the point is folded at once, even if it is many widths away from the range.
+fn fold<P>(sample: P, min: P, max: P) -> P
+where
+    P: Copy + Additive + Rem + PartialOrd + Zero,
+{
+    let width = max - min;
+    if width <= P::zero() {
+        return min;
+    }
+    let period = width + width;
+    let mut offset = (sample - min) % period;
+    if offset < P::zero() {
+        offset = offset + period;
+    }
+    if offset > width {
+        offset = period - offset;
+    }
+    min + offset
+}
+
+/// Natural logarithm of [`f64::EPSILON`].
+const LOG_EPSILON: f64 = -52.0 * core::f64::consts::LN_2;
+
+/// Maximal number of the periods to sum up the mirror images over, see [`sum_periods`].
+///
+/// Light-tailed kernels converge within a few periods, unless their bandwidth is much wider
+/// than the range. The images of heavy-tailed kernels, like [`crate::kernel::continuous::Cauchy`],
+/// decay only polynomially, so their sum gets cut here: the cut-off Cauchy mass is about
+/// `σ / (π · MAX_PERIODS · width)`.
+const MAX_PERIODS: usize = 100;
+
+/// Sum up the terms over the mirror images, which [`fold`] maps into the range.
+///
+/// The images repeat with the period of the double range width, and `term` is called with
+/// the image of `min` in each period. The periods go outwards from the range in pairs,
+/// until a pair becomes negligible and stops growing, but no further than [`MAX_PERIODS`].
+/// Kernels with compact support stop as soon as their images leave the support.
+fn sum_periods<P, T>(
+    min: P,
+    max: P,
+    term: impl Fn(P) -> T,
+    add: impl Fn(T, T) -> T,
+    is_negligible: impl Fn(T, T) -> bool,
+) -> T
+where
+    P: Copy + Additive + PartialOrd,
+    T: Copy + PartialOrd,
+{
+    let mut sum = term(min);
+    if max <= min {
+        return sum;
+    }
+    let period = (max - min) + (max - min);
+    let (mut forward, mut backward, mut previous) = (min, min, sum);
+    for _ in 0..MAX_PERIODS {
+        forward = forward + period;
+        backward = backward - period;
+        let pair = add(term(forward), term(backward));
+        sum = add(sum, pair);
+        if is_negligible(pair, sum) && pair <= previous {
+            break;
+        }
+        previous = pair;
+    }
+    sum
+}
+
+impl<K, P> KernelDensityEstimator<vec::IntoIter<K>, P>
+where
+    K: Kernel<Param = P>,
+    P: Copy + PartialOrd + Zero + One + ToPrimitive + FromPrimitive,
+{
+    /// Construct the estimator from the sample points, for example, to use it as a prior.
+    ///
+    /// Each point gets a kernel of the same bandwidth, which is selected
+    /// by [Silverman's rule of thumb][1]: `0.9 * min(σ, IQR / 1.34) * n^(-1/5)`.
+    /// If the rule yields zero, for example, when all the points are equal,
+    /// the bandwidth falls back to one. The bandwidth is real-valued, see [`Kernel::try_new_scaled`],
+    /// and if a kernel cannot be constructed with it, the kernel gets the unit bandwidth instead.
+    ///
+    /// # Errors
+    ///
+    /// The function returns [`Error::NoSamples`], if there are no sample points: the estimator
+    /// would have no components to sample from.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a sample point cannot be converted to [`f64`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::vec;
+    ///
+    /// use hyperopt::{kde::KernelDensityEstimator, kernel::continuous::Gaussian, Density};
+    ///
+    /// let prior: KernelDensityEstimator<vec::IntoIter<Gaussian<f64>>, f64> =
+    ///     KernelDensityEstimator::from_samples([0.9, 1.0, 1.1, 1.5]).unwrap();
+    /// assert!(prior.density(1.0) > prior.density(2.0));
+    /// ```
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Kernel_density_estimation#A_rule-of-thumb_bandwidth_estimator
+    pub fn from_samples(samples: impl IntoIterator<Item = P>) -> Result<Self, Error> {
+        let samples: Vec<P> = samples.into_iter().collect();
+        let bandwidth = silverman_bandwidth(samples.iter().map(|sample| sample.to_f64().unwrap()))
+            .ok_or(Error::NoSamples)?;
+        let bandwidth = if bandwidth > 0.0 { bandwidth } else { 1.0 };
+        let components: Vec<K> = samples
+            .into_iter()
+            .map(|sample| {
+                K::try_new_scaled(sample, bandwidth, None)
+                    .unwrap_or_else(|_| K::new(sample, P::one()))
+            })
+            .collect();
+        Ok(Self::new(components.into_iter()))
+    }
+}
+
+/// Select the bandwidth by [Silverman's rule of thumb][1].
+///
+/// Returns [`None`] for an empty sample.
+///
+/// [1]: https://en.wikipedia.org/wiki/Kernel_density_estimation#A_rule-of-thumb_bandwidth_estimator
+#[allow(clippy::cast_precision_loss)]
+fn silverman_bandwidth(samples: impl Iterator<Item = f64>) -> Option<f64> {
+    let mut samples: Vec<f64> = samples.collect();
+    if samples.is_empty() {
+        return None;
+    }
+    samples.sort_by(f64::total_cmp);
+
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let std = (samples
+        .iter()
+        .map(|sample| (sample - mean).powi(2))
+        .sum::<f64>()
+        / n)
+        .sqrt();
+
+    // Quantile with the linear interpolation between the closest ranks:
+    let quantile = |probability: f64| {
+        let rank = probability * (n - 1.0);
+        let (lower, fraction) = (rank.floor(), rank.fract());
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let lower = lower as usize;
+        let upper = (lower + 1).min(samples.len() - 1);
+        fraction.mul_add(samples[upper] - samples[lower], samples[lower])
+    };
+    let iqr = quantile(0.75) - quantile(0.25);
+
+    // Ignore the zero IQR, which happens for the heavily tied samples:
+    let spread = if iqr > 0.0 { std.min(iqr / 1.34) } else { std };
+    Some(0.9 * spread * n.powf(-0.2))
+}
+
+impl<Ks, P> Density for KernelDensityEstimator<Ks, P>
 where
     Ks: Iterator + Clone,
-    Ks::Item: Density,
-    <<Ks as Iterator>::Item as Density>::Param: Copy,
-    <<Ks as Iterator>::Item as Density>::Output: Arithmetic + FromPrimitive + Zero,
+    Ks::Item: Density<Param = P>,
+    P: Copy + Additive + PartialOrd,
+    <<Ks as Iterator>::Item as Density>::Output:
+        Copy + Arithmetic + PartialOrd + Exp + FromPrimitive + Zero + One,
 {
-    type Param = <<Ks as Iterator>::Item as Density>::Param;
+    type Param = P;
     type Output = <<Ks as Iterator>::Item as Density>::Output;
 
     /// Calculate the KDE's density at the specified point.
@@ -34,11 +272,28 @@ where
     /// The method returns [`P::zero()`], if there are no components.
     #[allow(clippy::cast_precision_loss)]
     fn density(&self, at: Self::Param) -> Self::Output {
+        let epsilon = Self::Output::from_f64(f64::EPSILON).unwrap();
+        let component_density = |component: &Ks::Item| match self.reflection {
+            Some((min, max)) if (min..=max).contains(&at) => {
+                let offset = at - min;
+                sum_periods(
+                    min,
+                    max,
+                    |origin| {
+                        component.density(origin + offset) + component.density(origin - offset)
+                    },
+                    |lhs, rhs| lhs + rhs,
+                    |pair, sum| pair <= sum * epsilon,
+                )
+            }
+            Some(_) => Self::Output::zero(),
+            None => component.density(at),
+        };
         let (n_points, sum) = self
-            .0
+            .components
             .clone()
             .fold((0_usize, Self::Output::zero()), |(n, sum), component| {
-                (n + 1, sum + component.density(at))
+                (n + 1, sum + component_density(&component))
             });
         if n_points == 0 {
             Self::Output::zero()
@@ -46,53 +301,413 @@ where
             sum / Self::Output::from_usize(n_points).unwrap()
         }
     }
+
+    /// Calculate the KDE's log-density as the log-sum-exp `ln Σ exp(xᵢ)`
+    /// of the components' log-densities, which does not underflow in the tails.
+    ///
+    /// The method returns `-∞`, if there are no components.
+    fn log_density(&self, at: Self::Param) -> Self::Output
+    where
+        Self::Output: Ln,
+    {
+        let log_epsilon = Self::Output::from_f64(LOG_EPSILON).unwrap();
+        let component_log_density = |component: &Ks::Item| match self.reflection {
+            Some((min, max)) if (min..=max).contains(&at) => {
+                let offset = at - min;
+                sum_periods(
+                    min,
+                    max,
+                    |origin| {
+                        log_sum_exp([
+                            component.log_density(origin + offset),
+                            component.log_density(origin - offset),
+                        ])
+                    },
+                    |lhs, rhs| log_sum_exp([lhs, rhs]),
+                    |pair, sum| pair <= sum + log_epsilon,
+                )
+            }
+            Some(_) => Self::Output::zero().ln(),
+            None => component.log_density(at),
+        };
+        let mut n_points = 0_usize;
+        let log_sum = log_sum_exp(self.components.clone().map(|component| {
+            n_points += 1;
+            component_log_density(&component)
+        }));
+        if n_points == 0 {
+            log_sum
+        } else {
+            log_sum - Self::Output::from_usize(n_points).unwrap().ln()
+        }
+    }
+
+    /// Calculate the KDE's log-mass as the log-sum-exp `ln Σ exp(xᵢ)`
+    /// of the components' log-masses.
+    ///
+    /// With the reflection, the interval is clamped into the bounds, and the mirrored components'
+    /// masses are accounted for.
+    ///
+    /// The method returns `-∞`, if there are no components.
+    fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+    where
+        Self::Param: Copy + ToPrimitive + FromPrimitive,
+        Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        let Range { start, end } = interval;
+        let log_epsilon = Self::Output::from_f64(LOG_EPSILON).unwrap();
+        let component_log_mass = |component: &Ks::Item| match self.reflection {
+            Some((min, max)) => {
+                let (start, end) = (
+                    num_traits::clamp(start, min, max) - min,
+                    num_traits::clamp(end, min, max) - min,
+                );
+                sum_periods(
+                    min,
+                    max,
+                    |origin| {
+                        log_sum_exp([
+                            component.log_mass(origin + start..origin + end),
+                            component.log_mass(origin - end..origin - start),
+                        ])
+                    },
+                    |lhs, rhs| log_sum_exp([lhs, rhs]),
+                    |pair, sum| pair <= sum + log_epsilon,
+                )
+            }
+            None => component.log_mass(start..end),
+        };
+        let mut n_points = 0_usize;
+        let log_sum = log_sum_exp(self.components.clone().map(|component| {
+            n_points += 1;
+            component_log_mass(&component)
+        }));
+        if n_points == 0 {
+            log_sum
+        } else {
+            log_sum - Self::Output::from_usize(n_points).unwrap().ln()
+        }
+    }
 }
 
-impl<Ks> Sample for KernelDensityEstimator<Ks>
+impl<Ks, P> Cdf for KernelDensityEstimator<Ks, P>
 where
     Ks: Iterator + Clone,
-    Ks::Item: Sample,
+    Ks::Item: Cdf<Param = P>,
+    P: Copy + Additive + PartialOrd,
+    <<Ks as Iterator>::Item as Cdf>::Output: Copy + Arithmetic + PartialOrd + FromPrimitive + Zero,
 {
-    type Param = Option<<<Ks as Iterator>::Item as Sample>::Param>;
+    type Param = P;
+    type Output = <<Ks as Iterator>::Item as Cdf>::Output;
 
-    /// Sample a random point from the KDE.
+    /// Calculate the mixture CDF, which is the average of the components' CDFs.
     ///
-    /// The algorithm uses «[reservoir sampling][1]» to pick a random component,
-    /// and then samples a point from that component.
+    /// With the reflection, the mirrored components' masses within the bounds are accounted for,
+    /// and the point is clamped into the bounds.
     ///
-    /// The method returns [`None`], if the estimator has no components.
+    /// The method returns zero, if there are no components.
+    fn cdf(&self, at: Self::Param) -> Self::Output {
+        let epsilon = Self::Output::from_f64(f64::EPSILON).unwrap();
+        let component_cdf = |component: &Ks::Item| match self.reflection {
+            Some((min, max)) => {
+                let offset = num_traits::clamp(at, min, max) - min;
+                // Integrate both images of the reflected density from `min` to `at`:
+                sum_periods(
+                    min,
+                    max,
+                    |origin| component.cdf(origin + offset) - component.cdf(origin - offset),
+                    |lhs, rhs| lhs + rhs,
+                    |pair, sum| pair <= sum * epsilon,
+                )
+            }
+            None => component.cdf(at),
+        };
+        let (n_points, sum) = self
+            .components
+            .clone()
+            .fold((0_usize, Self::Output::zero()), |(n, sum), component| {
+                (n + 1, sum + component_cdf(&component))
+            });
+        if n_points == 0 {
+            Self::Output::zero()
+        } else {
+            sum / Self::Output::from_usize(n_points).unwrap()
+        }
+    }
+}
+
+impl<Ks, P> Sample for KernelDensityEstimator<Ks, P>
+where
+    Ks: Iterator + Clone,
+    Ks::Item: Sample<Param = P>,
+    P: Copy + Additive + Rem + PartialOrd + Zero,
+{
+    type Param = P;
+
+    /// Sample a random point from the KDE.
     ///
-    /// [1]: https://en.wikipedia.org/wiki/Reservoir_sampling
+    /// # Panics
+    ///
+    /// This method panics if the estimator has no components, see [`KernelDensityEstimator::try_sample`]
+    /// for the non-panicking version.
     fn sample(&self, rng: &mut Rng) -> Self::Param {
-        let sample = self
-            .0
-            .clone()
-            .enumerate()
-            .filter(|(i, _)| rng.usize(0..=*i) == 0)
-            .last()?
-            .1
-            .sample(rng);
-        Some(sample)
+        self.try_sample(rng)
+            .expect("the estimator should have at least one component")
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::iter;
+    use core::{cell::Cell, f64::consts::PI, iter};
+
+    use approx::assert_abs_diff_eq;
 
     use super::*;
-    use crate::kernel::universal::Uniform;
+    use crate::kernel::{
+        continuous::{Cauchy, Epanechnikov, Gaussian},
+        discrete::DiscreteGaussian,
+        universal::Uniform,
+        Kernel,
+    };
+
+    /// Kernel, which counts its density evaluations.
+    #[derive(Clone)]
+    struct Counting<'a, K> {
+        kernel: K,
+        n_evaluations: &'a Cell<usize>,
+    }
+
+    impl<K: Density> Density for Counting<'_, K> {
+        type Param = K::Param;
+        type Output = K::Output;
+
+        fn density(&self, at: Self::Param) -> Self::Output {
+            self.n_evaluations.set(self.n_evaluations.get() + 1);
+            self.kernel.density(at)
+        }
+    }
 
     #[test]
     fn sample_single_component_ok() {
         let kernel: Uniform<_, ()> = Uniform::with_bounds(-1.0..=1.0);
-        let kde = KernelDensityEstimator(iter::once(kernel));
-        let mut rng = Rng::new();
+        let kde = KernelDensityEstimator::new(iter::once(kernel));
+        let mut rng = Rng::with_seed(42);
 
-        let sample = kde.sample(&mut rng).unwrap();
+        let sample = kde.sample(&mut rng);
         assert!((-1.0..=1.0).contains(&sample));
 
         // Ensure that the iterator can be reused.
-        let _ = kde.sample(&mut rng).unwrap();
+        let _ = kde.sample(&mut rng);
+    }
+
+    #[test]
+    fn try_sample_empty_ok() {
+        let kde = KernelDensityEstimator::<_, f64>::new(iter::empty::<Gaussian<f64>>());
+        assert_eq!(kde.try_sample(&mut Rng::with_seed(42)), None);
+    }
+
+    #[test]
+    fn from_samples_ok() {
+        let samples = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let kde: KernelDensityEstimator<vec::IntoIter<Gaussian<f64>>, f64> =
+            KernelDensityEstimator::from_samples(samples).unwrap();
+
+        // σ = √2 is less than IQR / 1.34 = 2 / 1.34:
+        let bandwidth = 0.9 * 2.0_f64.sqrt() * 5.0_f64.powf(-0.2);
+        let expected = KernelDensityEstimator::new(
+            samples
+                .map(|sample| Gaussian::new(sample, bandwidth))
+                .into_iter(),
+        );
+        assert_abs_diff_eq!(kde.density(2.5), expected.density(2.5), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn from_samples_empty_ok() {
+        let result: Result<KernelDensityEstimator<vec::IntoIter<Gaussian<f64>>, f64>, _> =
+            KernelDensityEstimator::from_samples([]);
+        assert_eq!(result.err(), Some(Error::NoSamples));
+    }
+
+    #[test]
+    fn from_samples_tied_ok() {
+        let kde: KernelDensityEstimator<vec::IntoIter<Gaussian<f64>>, f64> =
+            KernelDensityEstimator::from_samples([1.0, 1.0]).unwrap();
+        assert_abs_diff_eq!(kde.density(1.0), 0.398_942_280_401_432_7, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn from_samples_discrete_ok() {
+        let samples = [0, 1, 2, 3, 4];
+        let kde: KernelDensityEstimator<vec::IntoIter<DiscreteGaussian<i32, f64>>, i32> =
+            KernelDensityEstimator::from_samples(samples).unwrap();
+
+        // The bandwidth is below one, yet it does not get truncated:
+        let bandwidth = 0.9 * 2.0_f64.sqrt() * 5.0_f64.powf(-0.2);
+        let expected = KernelDensityEstimator::new(
+            samples
+                .map(|sample| DiscreteGaussian::try_new_scaled(sample, bandwidth, None).unwrap())
+                .into_iter(),
+        );
+        assert_abs_diff_eq!(kde.density(2), expected.density(2), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn silverman_bandwidth_ok() {
+        assert_eq!(silverman_bandwidth(iter::empty()), None);
+        assert_abs_diff_eq!(
+            silverman_bandwidth([1.0, 2.0, 3.0, 4.0, 5.0].into_iter()).unwrap(),
+            0.9 * 2.0_f64.sqrt() * 5.0_f64.powf(-0.2),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn reflection_density_ok() {
+        let kde =
+            KernelDensityEstimator::new(iter::once(Gaussian::new(0.0, 1.0))).reflection(0.0..=10.0);
+        assert_abs_diff_eq!(kde.density(-0.1), 0.0);
+        assert_abs_diff_eq!(kde.density(0.0), 0.797_884_560_802_865_4, epsilon = 1e-9);
+        assert_abs_diff_eq!(kde.density(11.0), 0.0);
+    }
+
+    #[test]
+    fn log_density_ok() {
+        let kde = KernelDensityEstimator::new(
+            [Gaussian::new(0.0, 1.0), Gaussian::new(1.0, 1.0)].into_iter(),
+        );
+        assert_abs_diff_eq!(
+            kde.log_density(0.5),
+            libm::log(kde.density(0.5)),
+            epsilon = 1e-12
+        );
+
+        // The density underflows, but the log-density does not:
+        assert_abs_diff_eq!(kde.density(100.0), 0.0);
+        assert_abs_diff_eq!(
+            kde.log_density(100.0),
+            -4_902.112_085_713_765,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn cdf_ok() {
+        let kde = KernelDensityEstimator::new(
+            [Gaussian::new(0.0, 1.0), Gaussian::new(2.0, 1.0)].into_iter(),
+        );
+        assert_abs_diff_eq!(kde.cdf(1.0), 0.5, epsilon = 1e-12);
+        assert_abs_diff_eq!(kde.cdf(0.0), 0.261_375_065_974_089_6, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn reflection_cdf_ok() {
+        let kde =
+            KernelDensityEstimator::new(iter::once(Gaussian::new(0.0, 1.0))).reflection(0.0..=10.0);
+        assert_abs_diff_eq!(kde.cdf(-1.0), 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kde.cdf(0.0), 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kde.cdf(1.0), 0.682_689_492_137_085_9, epsilon = 1e-9);
+        assert_abs_diff_eq!(kde.cdf(10.0), 1.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kde.cdf(11.0), 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn reflection_log_mass_ok() {
+        let kde =
+            KernelDensityEstimator::new(iter::once(Gaussian::new(0.0, 1.0))).reflection(0.0..=10.0);
+        assert_abs_diff_eq!(kde.log_mass(-1.0..11.0), 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            kde.log_mass(0.0..1.0),
+            0.682_689_492_137_085_9_f64.ln(),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn reflection_wide_bandwidth_integrates_to_one_ok() {
+        let kde =
+            KernelDensityEstimator::new(iter::once(Gaussian::new(0.5, 1.0))).reflection(0.0..=1.0);
+
+        // Trapezoidal rule:
+        let n_steps = 1000;
+        let integral = (0..=n_steps)
+            .map(|i| {
+                let weight = if i == 0 || i == n_steps { 0.5 } else { 1.0 };
+                weight * kde.density(f64::from(i) / f64::from(n_steps))
+            })
+            .sum::<f64>()
+            / f64::from(n_steps);
+        assert_abs_diff_eq!(integral, 1.0, epsilon = 1e-6);
+
+        assert_abs_diff_eq!(kde.density(0.5), 1.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(kde.log_density(0.5), 0.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(kde.cdf(1.0), 1.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kde.cdf(0.25), 0.25, epsilon = 1e-6);
+        assert_abs_diff_eq!(kde.log_mass(0.0..1.0), 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn reflection_compact_support_bounded_ok() {
+        let n_evaluations = Cell::new(0);
+        let kernel = Counting {
+            kernel: Epanechnikov::new(0.5, 10.0),
+            n_evaluations: &n_evaluations,
+        };
+        let kde = KernelDensityEstimator::new(iter::once(kernel)).reflection(0.0..=1.0);
+        assert_abs_diff_eq!(kde.density(0.5), 1.0, epsilon = 1e-3);
+
+        // The support is `±√5σ`, which is about 11 periods on each side:
+        assert!(n_evaluations.get() <= 4 * 13 + 2);
+    }
+
+    #[test]
+    fn reflection_heavy_tails_bounded_ok() {
+        let n_evaluations = Cell::new(0);
+        let kernel = Counting {
+            kernel: Cauchy::new(0.2, 0.1),
+            n_evaluations: &n_evaluations,
+        };
+        let kde = KernelDensityEstimator::new(iter::once(kernel)).reflection(0.0..=1.0);
+
+        // Closed-form sum of the Cauchy density over the period of `2`:
+        let periodic = |x: f64| 0.5 * (PI * 0.1).sinh() / ((PI * 0.1).cosh() - (PI * x).cos());
+        for x in [0.0, 0.2, 0.5, 1.0] {
+            n_evaluations.set(0);
+            let expected = periodic(x - 0.2) + periodic(x + 0.2);
+            // Each of the four cut-off image sequences loses about `σ / (4π · MAX_PERIODS)`:
+            assert_abs_diff_eq!(kde.density(x), expected, epsilon = 5e-4);
+            assert!(n_evaluations.get() <= 4 * MAX_PERIODS + 2);
+        }
+    }
+
+    #[test]
+    fn reflection_sample_ok() {
+        let kde =
+            KernelDensityEstimator::new(iter::once(Gaussian::new(0.0, 1.0))).reflection(0.0..=10.0);
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..100 {
+            assert!((0.0..=10.0).contains(&kde.sample(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn reflection_sample_wide_bandwidth_ok() {
+        let kde = KernelDensityEstimator::new(iter::once(Gaussian::new(0.5, 100.0)))
+            .reflection(0.0..=1.0);
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..100 {
+            assert!((0.0..=1.0).contains(&kde.sample(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn fold_ok() {
+        assert_abs_diff_eq!(fold(-0.25, 0.0, 1.0), 0.25);
+        assert_abs_diff_eq!(fold(1.25, 0.0, 1.0), 0.75);
+        assert_abs_diff_eq!(fold(-2.25, 0.0, 1.0), 0.25);
+        assert_abs_diff_eq!(fold(3.25, 0.0, 1.0), 0.75);
+        assert_eq!(fold(-7, 0, 3), 1);
+        assert_eq!(fold(11, 0, 3), 1);
+        assert_eq!(fold(5, 2, 2), 2);
     }
 }
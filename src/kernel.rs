@@ -5,10 +5,21 @@
 //!
 //! [1]: https://en.wikipedia.org/wiki/Kernel_(statistics)
 
+use core::ops::{Range, RangeInclusive};
+
 use fastrand::Rng;
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+
+use crate::{
+    iter::log_sum_exp,
+    traits::ops::{Arithmetic, Exp, Ln},
+    Error,
+};
 
 pub mod continuous;
 pub mod discrete;
+pub mod mixture;
+pub mod truncated;
 pub mod universal;
 
 /// Density function.
@@ -22,6 +33,46 @@ pub trait Density {
     /// Calculate the density at the given point.
     #[must_use]
     fn density(&self, at: Self::Param) -> Self::Output;
+
+    /// Calculate the natural logarithm of the density at the given point.
+    ///
+    /// Unlike the density itself, it does not underflow to zero in the tails,
+    /// so kernels should override it, when they can calculate it directly.
+    #[must_use]
+    fn log_density(&self, at: Self::Param) -> Self::Output
+    where
+        Self::Output: Ln,
+    {
+        self.density(at).ln()
+    }
+
+    /// Calculate the natural logarithm of the probability mass within the half-open interval
+    /// `[start, end)`.
+    ///
+    /// By default, the mass is approximated by [Simpson's rule][1] over the log-densities,
+    /// so that it does not underflow either. Kernels, which implement [`Cdf`], should override it
+    /// with the exact `cdf(end) - cdf(start)`, or `cdf(end - 1) - cdf(start - 1)` for the discrete ones.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Simpson%27s_rule
+    #[must_use]
+    fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+    where
+        Self::Param: Copy + ToPrimitive + FromPrimitive,
+        Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        let Range { start, end } = interval;
+        let (start_f64, end_f64) = (start.to_f64().unwrap(), end.to_f64().unwrap());
+        let middle = Self::Param::from_f64(0.5 * (start_f64 + end_f64)).unwrap();
+        let log_sum = log_sum_exp([
+            self.log_density(start),
+            Self::Output::from_u8(4).unwrap().ln() + self.log_density(middle),
+            self.log_density(end),
+        ]);
+        log_sum
+            + Self::Output::from_f64((end_f64 - start_f64) / 6.0)
+                .unwrap()
+                .ln()
+    }
 }
 
 /// Parameter sampler.
@@ -36,6 +87,36 @@ pub trait Sample {
     fn sample(&self, rng: &mut Rng) -> Self::Param;
 }
 
+/// [Cumulative distribution function][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/Cumulative_distribution_function
+pub trait Cdf {
+    /// Parameter type.
+    type Param;
+
+    /// Output probability type.
+    type Output;
+
+    /// Calculate the probability that a sample is less than or equal to the given point.
+    #[must_use]
+    fn cdf(&self, at: Self::Param) -> Self::Output;
+}
+
+/// [Quantile function][1], also known as the inverse cumulative distribution function.
+///
+/// [1]: https://en.wikipedia.org/wiki/Quantile_function
+pub trait Quantile {
+    /// Parameter type.
+    type Param;
+
+    /// Probability type.
+    type Probability;
+
+    /// Find the point, at which the cumulative distribution function reaches the probability.
+    #[must_use]
+    fn quantile(&self, probability: Self::Probability) -> Self::Param;
+}
+
 /// Kernel builder for [`crate::kde::KernelDensityEstimator`].
 ///
 /// Note that it does not directly correspond to the [mathematical definition][1],
@@ -49,4 +130,90 @@ pub trait Kernel {
     /// Construct a kernel with the given location and bandwidth.
     #[must_use]
     fn new(location: Self::Param, std: Self::Param) -> Self;
+
+    /// Construct a kernel with the given location and bandwidth, knowing the search range.
+    ///
+    /// [`crate::Optimizer`] always constructs the kernels this way. By default, the bounds are ignored,
+    /// and the optimizer merely clamps the samples to the search range, which piles the mass
+    /// up on the bounds. Truncation is opt-in: wrap the kernel into [`truncated::Truncated`],
+    /// for example, `Truncated<Epanechnikov<f64>>`.
+    #[must_use]
+    fn new_bounded(
+        location: Self::Param,
+        std: Self::Param,
+        bounds: RangeInclusive<Self::Param>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = bounds;
+        Self::new(location, std)
+    }
+
+    /// Construct a kernel with the given location and bandwidth, just like [`Kernel::new`],
+    /// but return an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// By default, returns [`Error::InvalidBandwidth`], if the bandwidth is not positive.
+    fn try_new(location: Self::Param, std: Self::Param) -> Result<Self, Error>
+    where
+        Self: Sized,
+        Self::Param: PartialOrd + Zero,
+    {
+        if std > Self::Param::zero() {
+            Ok(Self::new(location, std))
+        } else {
+            Err(Error::InvalidBandwidth)
+        }
+    }
+
+    /// Construct a kernel knowing the search range, just like [`Kernel::new_bounded`],
+    /// but return an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// By default, returns [`Error::InvalidBandwidth`], if the bandwidth is not positive.
+    fn try_new_bounded(
+        location: Self::Param,
+        std: Self::Param,
+        bounds: RangeInclusive<Self::Param>,
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+        Self::Param: PartialOrd + Zero,
+    {
+        if std > Self::Param::zero() {
+            Ok(Self::new_bounded(location, std, bounds))
+        } else {
+            Err(Error::InvalidBandwidth)
+        }
+    }
+
+    /// Construct a kernel with the real-valued bandwidth, optionally knowing the search range.
+    ///
+    /// [`crate::Optimizer`] always constructs the kernels this way, so that the bandwidth
+    /// of the discrete kernels is not limited to integers. By default, the bandwidth gets converted
+    /// to the parameter type, and the kernel is constructed with [`Kernel::try_new_bounded`]
+    /// or [`Kernel::try_new`].
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ConversionFailed`]: the bandwidth cannot be converted to the parameter type
+    /// - any error of [`Kernel::try_new_bounded`] or [`Kernel::try_new`]
+    fn try_new_scaled(
+        location: Self::Param,
+        std: f64,
+        bounds: Option<RangeInclusive<Self::Param>>,
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+        Self::Param: PartialOrd + Zero + FromPrimitive,
+    {
+        let std = Self::Param::from_f64(std).ok_or(Error::ConversionFailed)?;
+        match bounds {
+            Some(bounds) => Self::try_new_bounded(location, std, bounds),
+            None => Self::try_new(location, std),
+        }
+    }
 }
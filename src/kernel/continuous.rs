@@ -1,6 +1,70 @@
 //! Continuous kernels.
 
+mod biweight;
+mod cauchy;
+mod cosine;
 mod epanechnikov;
 mod gaussian;
+mod laplace;
+mod logistic;
+mod triangular;
+mod triweight;
+mod von_mises;
 
-pub use self::{epanechnikov::Epanechnikov, gaussian::Gaussian};
+use fastrand::Rng;
+
+pub use self::{
+    biweight::Biweight,
+    cauchy::Cauchy,
+    cosine::Cosine,
+    epanechnikov::Epanechnikov,
+    gaussian::Gaussian,
+    laplace::Laplace,
+    logistic::Logistic,
+    triangular::Triangular,
+    triweight::Triweight,
+    von_mises::VonMises,
+};
+pub(crate) use self::gaussian::standard_normal_quantile;
+
+/// Sample the median of `N` iid uniform samples, `N` is odd.
+///
+/// The median follows the [beta distribution][1] `Beta((N + 1) / 2, (N + 1) / 2)`.
+///
+/// [1]: https://en.wikipedia.org/wiki/Order_statistic#Order_statistics_sampled_from_a_uniform_distribution
+fn sample_uniform_median<const N: usize>(rng: &mut Rng) -> f64 {
+    let mut samples = [0.0; N];
+    samples.fill_with(|| rng.f64());
+    samples.sort_unstable_by(f64::total_cmp);
+    samples[N / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use fastrand::Rng;
+
+    use crate::Sample;
+
+    /// Assert that the kernel samples have zero mean and unit standard deviation.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn assert_standardized(kernel: &impl Sample<Param = f64>) {
+        const N_SAMPLES: usize = 100_000;
+
+        let mut rng = Rng::with_seed(42);
+        let samples: Vec<f64> = (0..N_SAMPLES).map(|_| kernel.sample(&mut rng)).collect();
+        let mean = samples.iter().sum::<f64>() / N_SAMPLES as f64;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample - mean).powi(2))
+            .sum::<f64>()
+            / N_SAMPLES as f64;
+        assert!(mean.abs() < 0.02, "mean: {mean}");
+        assert!(
+            (variance.sqrt() - 1.0).abs() < 0.02,
+            "std: {}",
+            variance.sqrt()
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::FromPrimitive;
+
+use crate::{
+    constants::{ConstFifteenSixteenths, ConstSqrt7},
+    kernel::{continuous::sample_uniform_median, Density, Kernel, Sample},
+    traits::ops::{Arithmetic, Neg},
+};
+
+/// [Standardized][1] biweight (quartic) kernel, over (-√7, +√7) range.
+///
+/// [1]: https://en.wikipedia.org/wiki/Kernel_(statistics)#Kernel_functions_in_common_use
+#[derive(Copy, Clone, Debug)]
+pub struct Biweight<T> {
+    location: T,
+    std: T,
+}
+
+impl<T> Density for Biweight<T>
+where
+    T: Arithmetic
+        + Copy
+        + PartialOrd
+        + Neg
+        + num_traits::One
+        + num_traits::Zero
+        + ConstSqrt7
+        + ConstFifteenSixteenths,
+{
+    type Param = T;
+    type Output = T;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        // Scale to `-1..1`:
+        let normalized = (at - self.location) / self.std / T::SQRT_7;
+        if (-T::one()..=T::one()).contains(&normalized) {
+            let base = T::one() - normalized * normalized;
+            T::FIFTEEN_SIXTEENTHS / T::SQRT_7 * base * base / self.std
+        } else {
+            T::zero()
+        }
+    }
+}
+
+impl<T> Sample for Biweight<T>
+where
+    T: Copy + Arithmetic + FromPrimitive,
+{
+    type Param = T;
+
+    /// Generate a sample from the scaled `Beta(3, 3)` distribution.
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        let normalized = sample_uniform_median::<5>(rng).mul_add(2.0, -1.0);
+        self.location + self.std * T::from_f64(normalized * f64::SQRT_7).unwrap()
+    }
+}
+
+impl<T> Kernel for Biweight<T>
+where
+    Self: Density<Param = T, Output = T> + Sample<Param = T>,
+    T: PartialOrd + num_traits::Zero,
+{
+    type Param = T;
+
+    fn new(location: T, std: T) -> Self {
+        assert!(std > T::zero());
+        Self { location, std }
+    }
+}
+
+impl<T> Default for Biweight<T>
+where
+    T: num_traits::Zero + num_traits::One,
+{
+    fn default() -> Self {
+        Self {
+            location: T::zero(),
+            std: T::one(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::kernel::continuous::tests::assert_standardized;
+
+    #[test]
+    fn density_ok() {
+        let kernel = Biweight::<f64>::default();
+        assert_abs_diff_eq!(kernel.density(0.0), 0.354_341_693_446_150_5);
+        assert_abs_diff_eq!(kernel.density(f64::SQRT_7), 0.0);
+        assert_abs_diff_eq!(kernel.density(-10.0), 0.0);
+    }
+
+    #[test]
+    fn sample_ok() {
+        assert_standardized(&Biweight::default());
+    }
+}
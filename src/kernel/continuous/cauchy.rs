@@ -0,0 +1,107 @@
+use core::f64::consts::PI;
+
+use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::FromPrimitive;
+
+use crate::{
+    constants::ConstFrac1Pi,
+    kernel::{Density, Kernel, Sample},
+    traits::ops::Arithmetic,
+};
+
+/// [Cauchy][1] (Lorentz) kernel.
+///
+/// The Cauchy distribution has no standard deviation, so the latter is used as the scale,
+/// which is the half-width at half-maximum. Its tails are very heavy, which makes
+/// the exploration the most aggressive among the kernels.
+///
+/// [1]: https://en.wikipedia.org/wiki/Cauchy_distribution
+#[derive(Copy, Clone, Debug)]
+pub struct Cauchy<T> {
+    location: T,
+    scale: T,
+}
+
+impl<T> Density for Cauchy<T>
+where
+    T: Copy + Arithmetic + num_traits::One + ConstFrac1Pi,
+{
+    type Param = T;
+    type Output = T;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        let normalized = (at - self.location) / self.scale;
+        T::FRAC_1_PI / (T::one() + normalized * normalized) / self.scale
+    }
+}
+
+impl<T> Sample for Cauchy<T>
+where
+    T: Copy + Arithmetic + FromPrimitive,
+{
+    type Param = T;
+
+    /// Generate a sample using the inverse cumulative distribution function.
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        let normalized = (PI * (rng.f64() - 0.5)).tan();
+        self.location + self.scale * T::from_f64(normalized).unwrap()
+    }
+}
+
+impl<T> Kernel for Cauchy<T>
+where
+    Self: Density<Param = T, Output = T> + Sample<Param = T>,
+    T: PartialOrd + num_traits::Zero,
+{
+    type Param = T;
+
+    fn new(location: T, std: T) -> Self {
+        assert!(std > T::zero());
+        Self {
+            location,
+            scale: std,
+        }
+    }
+}
+
+impl<T> Default for Cauchy<T>
+where
+    T: num_traits::Zero + num_traits::One,
+{
+    fn default() -> Self {
+        Self {
+            location: T::zero(),
+            scale: T::one(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::FRAC_1_PI;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn density_ok() {
+        let kernel = Cauchy::<f64>::default();
+        assert_abs_diff_eq!(kernel.density(0.0), FRAC_1_PI);
+        assert_abs_diff_eq!(kernel.density(1.0), FRAC_1_PI / 2.0);
+        assert_abs_diff_eq!(kernel.density(-1.0), FRAC_1_PI / 2.0);
+    }
+
+    #[test]
+    fn sample_ok() {
+        // Half of the samples should be within the scale:
+        let kernel = Cauchy::<f64>::default();
+        let mut rng = Rng::with_seed(42);
+        let n_inside = (0..10_000)
+            .filter(|_| (-1.0..=1.0).contains(&kernel.sample(&mut rng)))
+            .count();
+        assert!((4_800..=5_200).contains(&n_inside), "{n_inside}");
+    }
+}
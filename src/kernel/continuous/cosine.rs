@@ -0,0 +1,103 @@
+use core::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{FromPrimitive, ToPrimitive};
+
+use crate::{
+    constants::ConstCosineScale,
+    kernel::{Density, Kernel, Sample},
+    traits::ops::Arithmetic,
+};
+
+/// [Standardized][1] cosine kernel, over (-π / √(π² - 8), +π / √(π² - 8)) range.
+///
+/// [1]: https://en.wikipedia.org/wiki/Kernel_(statistics)#Kernel_functions_in_common_use
+#[derive(Copy, Clone, Debug)]
+pub struct Cosine<T> {
+    location: T,
+    std: T,
+}
+
+impl<T> Density for Cosine<T>
+where
+    T: Copy + Arithmetic + ToPrimitive + FromPrimitive,
+{
+    type Param = T;
+    type Output = T;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        // Scale to `-1..1`:
+        let normalized = ((at - self.location) / self.std).to_f64().unwrap() / f64::COSINE_SCALE;
+        let density = if (-1.0..=1.0).contains(&normalized) {
+            FRAC_PI_4 * (FRAC_PI_2 * normalized).cos() / f64::COSINE_SCALE
+        } else {
+            0.0
+        };
+        T::from_f64(density).unwrap() / self.std
+    }
+}
+
+impl<T> Sample for Cosine<T>
+where
+    T: Copy + Arithmetic + FromPrimitive,
+{
+    type Param = T;
+
+    /// Generate a sample using the inverse cumulative distribution function.
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        let normalized = rng.f64().mul_add(2.0, -1.0).asin() / FRAC_PI_2;
+        self.location + self.std * T::from_f64(normalized * f64::COSINE_SCALE).unwrap()
+    }
+}
+
+impl<T> Kernel for Cosine<T>
+where
+    Self: Density<Param = T, Output = T> + Sample<Param = T>,
+    T: PartialOrd + num_traits::Zero,
+{
+    type Param = T;
+
+    fn new(location: T, std: T) -> Self {
+        assert!(std > T::zero());
+        Self { location, std }
+    }
+}
+
+impl<T> Default for Cosine<T>
+where
+    T: num_traits::Zero + num_traits::One,
+{
+    fn default() -> Self {
+        Self {
+            location: T::zero(),
+            std: T::one(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::kernel::continuous::tests::assert_standardized;
+
+    #[test]
+    fn density_ok() {
+        let kernel = Cosine::<f64>::default();
+        assert_abs_diff_eq!(
+            kernel.density(0.0),
+            0.341_833_695_044_951_5,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(kernel.density(f64::COSINE_SCALE), 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.density(-10.0), 0.0);
+    }
+
+    #[test]
+    fn sample_ok() {
+        assert_standardized(&Cosine::default());
+    }
+}
@@ -1,11 +1,14 @@
-use std::fmt::Debug;
+use core::{fmt::Debug, ops::Range};
 
 use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
 
 use crate::{
-    constants::{ConstSqrt5, ConstThreeQuarters},
-    kernel::{Density, Kernel, Sample},
-    traits::ops::{Arithmetic, Neg},
+    constants::{ConstOneHalf, ConstOneQuarter, ConstSqrt5, ConstThreeQuarters},
+    kernel::{Cdf, Density, Kernel, Quantile, Sample},
+    traits::ops::{Arithmetic, Exp, Ln, Neg},
 };
 
 /// [Standardized][1] Epanechnikov (parabolic) kernel, over (-√5, +√5) range.
@@ -26,6 +29,8 @@ where
         + num_traits::One
         + num_traits::Zero
         + ConstSqrt5
+        + ConstOneHalf
+        + ConstOneQuarter
         + ConstThreeQuarters,
 {
     type Param = T;
@@ -42,6 +47,68 @@ where
             T::zero()
         }
     }
+
+    fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+    where
+        Self::Param: Copy + ToPrimitive + FromPrimitive,
+        Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        (self.cdf(interval.end) - self.cdf(interval.start)).ln()
+    }
+}
+
+impl<T> Cdf for Epanechnikov<T>
+where
+    T: Arithmetic
+        + Copy
+        + PartialOrd
+        + Neg
+        + num_traits::One
+        + num_traits::Zero
+        + ConstSqrt5
+        + ConstOneHalf
+        + ConstOneQuarter
+        + ConstThreeQuarters,
+{
+    type Param = T;
+    type Output = T;
+
+    fn cdf(&self, at: Self::Param) -> Self::Output {
+        // Scale to `-1..1`:
+        let normalized = (at - self.location) / self.std / T::SQRT_5;
+        if normalized <= -T::one() {
+            T::zero()
+        } else if normalized >= T::one() {
+            T::one()
+        } else {
+            // Integral of `¾(1 - x²)`:
+            T::ONE_HALF
+                + normalized * (T::THREE_QUARTERS - T::ONE_QUARTER * normalized * normalized)
+        }
+    }
+}
+
+impl<T> Quantile for Epanechnikov<T>
+where
+    T: Copy + Arithmetic + ToPrimitive + TryFrom<f64>,
+    <T as TryFrom<f64>>::Error: Debug,
+{
+    type Param = T;
+    type Probability = T;
+
+    /// Invert the CDF by solving the [depressed cubic][1] `x³ - 3x + 2(2p - 1) = 0`
+    /// in its trigonometric form.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Cubic_equation#Trigonometric_and_hyperbolic_solutions
+    fn quantile(&self, probability: Self::Probability) -> Self::Param {
+        let centered = probability
+            .to_f64()
+            .unwrap()
+            .mul_add(2.0, -1.0)
+            .clamp(-1.0, 1.0);
+        let normalized = 2.0 * (centered.asin() / 3.0).sin();
+        self.location + self.std * T::try_from(normalized * f64::SQRT_5).unwrap()
+    }
 }
 
 impl<T> Sample for Epanechnikov<T>
@@ -131,6 +198,28 @@ mod tests {
         assert_eq!(kernel.density(10.0), 0.0);
     }
 
+    #[test]
+    fn cdf_ok() {
+        let kernel = Epanechnikov::<f64>::default();
+        assert_abs_diff_eq!(kernel.cdf(-10.0), 0.0);
+        assert_abs_diff_eq!(kernel.cdf(0.0), 0.5);
+        assert_abs_diff_eq!(kernel.cdf(f64::SQRT_5 / 2.0), 0.843_75);
+        assert_abs_diff_eq!(kernel.cdf(10.0), 1.0);
+    }
+
+    #[test]
+    fn quantile_ok() {
+        let kernel = Epanechnikov::<f64>::default();
+        assert_abs_diff_eq!(kernel.quantile(0.0), -f64::SQRT_5, epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.quantile(0.5), 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            kernel.quantile(0.843_75),
+            f64::SQRT_5 / 2.0,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(kernel.quantile(1.0), f64::SQRT_5, epsilon = 1e-12);
+    }
+
     #[test]
     fn min_2_ok() {
         assert_eq!(min_2(1, 2, 3), (1, 2));
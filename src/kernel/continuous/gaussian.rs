@@ -1,12 +1,18 @@
-use std::{f64::consts::TAU, fmt::Debug};
+use core::{
+    f64::consts::{SQRT_2, TAU},
+    fmt::Debug,
+    ops::Range,
+};
 
 use fastrand::Rng;
-use num_traits::FromPrimitive;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
 
 use crate::{
-    constants::{ConstFrac1SqrtTau, ConstOneHalf},
-    kernel::{Density, Kernel, Sample},
-    traits::ops::{Arithmetic, Exp, Neg},
+    constants::{ConstFrac1SqrtTau, ConstLnFrac1SqrtTau, ConstOneHalf},
+    kernel::{Cdf, Density, Kernel, Quantile, Sample},
+    traits::ops::{Arithmetic, Exp, Ln, Neg},
 };
 
 /// [Gaussian][1] kernel.
@@ -20,7 +26,7 @@ pub struct Gaussian<T> {
 
 impl<T> Density for Gaussian<T>
 where
-    T: Copy + ConstFrac1SqrtTau + Arithmetic + ConstOneHalf + Exp + Neg,
+    T: Copy + ConstFrac1SqrtTau + ConstLnFrac1SqrtTau + Arithmetic + ConstOneHalf + Exp + Neg,
 {
     type Param = T;
     type Output = T;
@@ -29,6 +35,64 @@ where
         let normalized = (at - self.location) / self.std;
         T::FRAC_1_SQRT_TAU * (-T::ONE_HALF * normalized * normalized).exp() / self.std
     }
+
+    fn log_density(&self, at: Self::Param) -> Self::Output
+    where
+        Self::Output: Ln,
+    {
+        let normalized = (at - self.location) / self.std;
+        T::LN_FRAC_1_SQRT_TAU - T::ONE_HALF * normalized * normalized - self.std.ln()
+    }
+
+    /// Calculate the log-mass via the [complementary error function][1].
+    ///
+    /// In the upper tail, the mass is calculated via the survival function instead of the CDF,
+    /// so that it does not get lost in rounding the CDF to one.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Error_function#Complementary_error_function
+    fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+    where
+        Self::Param: Copy + ToPrimitive + FromPrimitive,
+        Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        let normalize = |at: T| ((at - self.location) / self.std).to_f64().unwrap() / SQRT_2;
+        let (start, end) = (normalize(interval.start), normalize(interval.end));
+        let mass = if start > 0.0 {
+            0.5 * (libm::erfc(start) - libm::erfc(end))
+        } else {
+            0.5 * (libm::erfc(-end) - libm::erfc(-start))
+        };
+        T::from_f64(mass).unwrap().ln()
+    }
+}
+
+impl<T> Cdf for Gaussian<T>
+where
+    T: Copy + Arithmetic + ToPrimitive + FromPrimitive,
+{
+    type Param = T;
+    type Output = T;
+
+    /// Calculate the CDF via the [complementary error function][1].
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Error_function#Complementary_error_function
+    fn cdf(&self, at: Self::Param) -> Self::Output {
+        let normalized = ((at - self.location) / self.std).to_f64().unwrap();
+        T::from_f64(0.5 * libm::erfc(-normalized / SQRT_2)).unwrap()
+    }
+}
+
+impl<T> Quantile for Gaussian<T>
+where
+    T: Copy + Arithmetic + ToPrimitive + FromPrimitive,
+{
+    type Param = T;
+    type Probability = T;
+
+    fn quantile(&self, probability: Self::Probability) -> Self::Param {
+        let normalized = standard_normal_quantile(probability.to_f64().unwrap());
+        self.location + self.std * T::from_f64(normalized).unwrap()
+    }
 }
 
 impl<T> Sample for Gaussian<T>
@@ -43,7 +107,7 @@ where
     fn sample(&self, rng: &mut Rng) -> Self::Param {
         let u1 = rng.f64();
         let u2 = rng.f64();
-        let normalized = T::from_f64((-2.0 * u1.ln()).sqrt() * (TAU * u2).cos()).unwrap();
+        let normalized = T::from_f64((-2.0 * libm::log(u1)).sqrt() * (TAU * u2).cos()).unwrap();
         self.location + self.std * normalized
     }
 }
@@ -74,11 +138,82 @@ where
     }
 }
 
+/// Calculate the standard normal quantile using [Acklam's algorithm][1],
+/// refined with a single step of [Halley's method][2].
+///
+/// [1]: https://web.archive.org/web/20151030215612/http://home.online.no/~pjacklam/notes/invnorm/
+/// [2]: https://en.wikipedia.org/wiki/Halley%27s_method
+#[allow(clippy::unreadable_literal)]
+pub fn standard_normal_quantile(probability: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 6] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+        1.0,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 5] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+        1.0,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let polynomial = |coefficients: &[f64], x: f64| {
+        coefficients
+            .iter()
+            .fold(0.0_f64, |sum, coefficient| sum.mul_add(x, *coefficient))
+    };
+    let tail = |probability: f64| {
+        let q = (-2.0 * libm::log(probability)).sqrt();
+        polynomial(&C, q) / polynomial(&D, q)
+    };
+
+    if probability <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if probability >= 1.0 {
+        return f64::INFINITY;
+    }
+    let approximation = if probability < P_LOW {
+        tail(probability)
+    } else if probability <= 1.0 - P_LOW {
+        let q = probability - 0.5;
+        polynomial(&A, q * q) * q / polynomial(&B, q * q)
+    } else {
+        -tail(1.0 - probability)
+    };
+
+    let error = 0.5_f64.mul_add(libm::erfc(-approximation / SQRT_2), -probability);
+    let u = error * TAU.sqrt() * libm::exp(approximation * approximation / 2.0);
+    approximation - u / approximation.mul_add(u / 2.0, 1.0)
+}
+
 #[cfg(test)]
 mod tests {
     use approx::assert_abs_diff_eq;
 
     use super::*;
+    use crate::Error;
 
     #[test]
     fn density_ok() {
@@ -87,4 +222,82 @@ mod tests {
         assert_abs_diff_eq!(kernel.density(1.0), 0.241_970_724_519_143_37,);
         assert_abs_diff_eq!(kernel.density(-1.0), 0.241_970_724_519_143_37,);
     }
+
+    #[test]
+    fn log_density_ok() {
+        let kernel = Gaussian::new(1.0, 2.0);
+        assert_abs_diff_eq!(
+            kernel.log_density(2.0),
+            libm::log(kernel.density(2.0)),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            kernel.log_density(101.0),
+            -1_250.0 - 2.0_f64.ln() - 0.918_938_533_204_672_8
+        );
+    }
+
+    #[test]
+    fn cdf_ok() {
+        let kernel = Gaussian::default();
+        assert_abs_diff_eq!(kernel.cdf(0.0), 0.5);
+        assert_abs_diff_eq!(kernel.cdf(1.0), 0.841_344_746_068_542_9, epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.cdf(-1.0), 0.158_655_253_931_457_05, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn log_mass_ok() {
+        let kernel = Gaussian::default();
+        assert_abs_diff_eq!(
+            kernel.log_mass(-1.0..1.0),
+            0.682_689_492_137_085_9_f64.ln(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            kernel.log_mass(9.0..10.0),
+            -43.628_216_632_280_82,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            kernel.log_mass(-10.0..-9.0),
+            -43.628_216_632_280_82,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn quantile_ok() {
+        let kernel = Gaussian::new(1.0, 2.0);
+        assert_abs_diff_eq!(kernel.quantile(0.5), 1.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            kernel.quantile(0.841_344_746_068_542_9),
+            3.0,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            kernel.quantile(0.001),
+            -5.180_464_612_335_626,
+            epsilon = 1e-9
+        );
+        for probability in [1e-10, 0.01, 0.3, 0.7, 0.99, 1.0 - 1e-10] {
+            assert_abs_diff_eq!(
+                kernel.cdf(kernel.quantile(probability)),
+                probability,
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn try_new_ok() {
+        assert!(Gaussian::try_new(0.0, 1.0).is_ok());
+        assert!(matches!(
+            Gaussian::try_new(0.0, 0.0),
+            Err(Error::InvalidBandwidth)
+        ));
+        assert!(matches!(
+            Gaussian::try_new(0.0, f64::NAN),
+            Err(Error::InvalidBandwidth)
+        ));
+    }
 }
@@ -0,0 +1,137 @@
+use fastrand::Rng;
+use num_traits::FromPrimitive;
+
+use crate::{
+    constants::{ConstFrac1Sqrt2, ConstSqrt2},
+    kernel::{Density, Kernel, Sample},
+    traits::ops::{Arithmetic, Exp, Ln, Neg},
+};
+
+/// [Standardized][1] Laplace (double exponential) kernel.
+///
+/// Its tails are heavier than the [`super::Gaussian`] ones, which favors exploration.
+///
+/// [1]: https://en.wikipedia.org/wiki/Laplace_distribution
+#[derive(Copy, Clone, Debug)]
+pub struct Laplace<T> {
+    location: T,
+    std: T,
+}
+
+impl<T> Density for Laplace<T>
+where
+    T: Copy + Arithmetic + PartialOrd + Exp + Neg + num_traits::Zero + ConstSqrt2 + ConstFrac1Sqrt2,
+{
+    type Param = T;
+    type Output = T;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        let normalized = (at - self.location) / self.std;
+        let abs_normalized = if normalized < T::zero() {
+            -normalized
+        } else {
+            normalized
+        };
+        T::FRAC_1_SQRT_2 * (-T::SQRT_2 * abs_normalized).exp() / self.std
+    }
+
+    fn log_density(&self, at: Self::Param) -> Self::Output
+    where
+        Self::Output: Ln,
+    {
+        let normalized = (at - self.location) / self.std;
+        let abs_normalized = if normalized < T::zero() {
+            -normalized
+        } else {
+            normalized
+        };
+        (T::FRAC_1_SQRT_2 / self.std).ln() - T::SQRT_2 * abs_normalized
+    }
+}
+
+impl<T> Sample for Laplace<T>
+where
+    T: Copy + Arithmetic + FromPrimitive,
+{
+    type Param = T;
+
+    /// Generate a sample as an exponentially distributed sample with a random sign.
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        let abs_normalized = -(1.0 - rng.f64()).ln() * f64::FRAC_1_SQRT_2;
+        let normalized = if rng.bool() {
+            abs_normalized
+        } else {
+            -abs_normalized
+        };
+        self.location + self.std * T::from_f64(normalized).unwrap()
+    }
+}
+
+impl<T> Kernel for Laplace<T>
+where
+    Self: Density<Param = T, Output = T> + Sample<Param = T>,
+    T: PartialOrd + num_traits::Zero,
+{
+    type Param = T;
+
+    fn new(location: T, std: T) -> Self {
+        assert!(std > T::zero());
+        Self { location, std }
+    }
+}
+
+impl<T> Default for Laplace<T>
+where
+    T: num_traits::Zero + num_traits::One,
+{
+    fn default() -> Self {
+        Self {
+            location: T::zero(),
+            std: T::one(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::kernel::continuous::tests::assert_standardized;
+
+    #[test]
+    fn density_ok() {
+        let kernel = Laplace::<f64>::default();
+        assert_abs_diff_eq!(kernel.density(0.0), f64::FRAC_1_SQRT_2);
+        assert_abs_diff_eq!(kernel.density(f64::FRAC_1_SQRT_2), 0.260_130_047_511_444_4);
+        assert_abs_diff_eq!(kernel.density(-f64::FRAC_1_SQRT_2), 0.260_130_047_511_444_4);
+    }
+
+    #[test]
+    fn log_density_ok() {
+        let kernel = Laplace::<f64>::new(1.0, 2.0);
+        assert_abs_diff_eq!(
+            kernel.log_density(-1.0),
+            kernel.density(-1.0).ln(),
+            epsilon = 1e-12
+        );
+        assert!(kernel.log_density(10_000.0).is_finite());
+    }
+
+    #[test]
+    fn log_mass_ok() {
+        // Simpson's rule by default:
+        let kernel = Laplace::<f64>::default();
+        assert_abs_diff_eq!(
+            kernel.log_mass(0.0..0.5),
+            -1.372_526_951_116_621,
+            epsilon = 1e-3
+        );
+        assert!(kernel.log_mass(10_000.0..10_001.0).is_finite());
+    }
+
+    #[test]
+    fn sample_ok() {
+        assert_standardized(&Laplace::default());
+    }
+}
@@ -0,0 +1,115 @@
+use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::FromPrimitive;
+
+use crate::{
+    constants::ConstFracPiSqrt3,
+    kernel::{Density, Kernel, Sample},
+    traits::ops::{Arithmetic, Exp, Neg},
+};
+
+/// [Standardized][1] logistic kernel.
+///
+/// [1]: https://en.wikipedia.org/wiki/Logistic_distribution
+#[derive(Copy, Clone, Debug)]
+pub struct Logistic<T> {
+    location: T,
+    std: T,
+}
+
+impl<T> Density for Logistic<T>
+where
+    T: Copy
+        + Arithmetic
+        + PartialOrd
+        + Exp
+        + Neg
+        + num_traits::One
+        + num_traits::Zero
+        + ConstFracPiSqrt3,
+{
+    type Param = T;
+    type Output = T;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        // Scale to the unit scale parameter:
+        let normalized = (at - self.location) / self.std * T::FRAC_PI_SQRT_3;
+
+        // The density is symmetric, and the negative exponent does not overflow:
+        let abs_normalized = if normalized < T::zero() {
+            -normalized
+        } else {
+            normalized
+        };
+        let exp = (-abs_normalized).exp();
+        let denominator = T::one() + exp;
+        T::FRAC_PI_SQRT_3 * exp / (denominator * denominator) / self.std
+    }
+}
+
+impl<T> Sample for Logistic<T>
+where
+    T: Copy + Arithmetic + FromPrimitive,
+{
+    type Param = T;
+
+    /// Generate a sample using the inverse cumulative distribution function.
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        // Avoid the infinite logit:
+        let probability = loop {
+            let probability = rng.f64();
+            if probability > 0.0 {
+                break probability;
+            }
+        };
+        let normalized = (probability / (1.0 - probability)).ln() / f64::FRAC_PI_SQRT_3;
+        self.location + self.std * T::from_f64(normalized).unwrap()
+    }
+}
+
+impl<T> Kernel for Logistic<T>
+where
+    Self: Density<Param = T, Output = T> + Sample<Param = T>,
+    T: PartialOrd + num_traits::Zero,
+{
+    type Param = T;
+
+    fn new(location: T, std: T) -> Self {
+        assert!(std > T::zero());
+        Self { location, std }
+    }
+}
+
+impl<T> Default for Logistic<T>
+where
+    T: num_traits::Zero + num_traits::One,
+{
+    fn default() -> Self {
+        Self {
+            location: T::zero(),
+            std: T::one(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::kernel::continuous::tests::assert_standardized;
+
+    #[test]
+    fn density_ok() {
+        let kernel = Logistic::<f64>::default();
+        assert_abs_diff_eq!(kernel.density(0.0), 0.453_449_841_058_554_46);
+        assert_abs_diff_eq!(kernel.density(1.0), kernel.density(-1.0));
+        assert_abs_diff_eq!(kernel.density(1000.0), 0.0);
+    }
+
+    #[test]
+    fn sample_ok() {
+        assert_standardized(&Logistic::default());
+    }
+}
@@ -0,0 +1,100 @@
+use fastrand::Rng;
+use num_traits::FromPrimitive;
+
+use crate::{
+    constants::ConstSqrt6,
+    kernel::{Density, Kernel, Sample},
+    traits::ops::{Arithmetic, Neg},
+};
+
+/// [Standardized][1] triangular kernel, over (-√6, +√6) range.
+///
+/// [1]: https://en.wikipedia.org/wiki/Kernel_(statistics)#Kernel_functions_in_common_use
+#[derive(Copy, Clone, Debug)]
+pub struct Triangular<T> {
+    location: T,
+    std: T,
+}
+
+impl<T> Density for Triangular<T>
+where
+    T: Arithmetic + Copy + PartialOrd + Neg + num_traits::One + num_traits::Zero + ConstSqrt6,
+{
+    type Param = T;
+    type Output = T;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        // Scale to `-1..1`:
+        let normalized = (at - self.location) / self.std / T::SQRT_6;
+        let abs_normalized = if normalized < T::zero() {
+            -normalized
+        } else {
+            normalized
+        };
+        if abs_normalized <= T::one() {
+            (T::one() - abs_normalized) / T::SQRT_6 / self.std
+        } else {
+            T::zero()
+        }
+    }
+}
+
+impl<T> Sample for Triangular<T>
+where
+    T: Copy + Arithmetic + FromPrimitive,
+{
+    type Param = T;
+
+    /// Generate a sample as a difference of two iid uniform samples.
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        let normalized = T::from_f64((rng.f64() - rng.f64()) * f64::SQRT_6).unwrap();
+        self.location + self.std * normalized
+    }
+}
+
+impl<T> Kernel for Triangular<T>
+where
+    Self: Density<Param = T, Output = T> + Sample<Param = T>,
+    T: PartialOrd + num_traits::Zero,
+{
+    type Param = T;
+
+    fn new(location: T, std: T) -> Self {
+        assert!(std > T::zero());
+        Self { location, std }
+    }
+}
+
+impl<T> Default for Triangular<T>
+where
+    T: num_traits::Zero + num_traits::One,
+{
+    fn default() -> Self {
+        Self {
+            location: T::zero(),
+            std: T::one(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::kernel::continuous::tests::assert_standardized;
+
+    #[test]
+    fn density_ok() {
+        let kernel = Triangular::<f64>::default();
+        assert_abs_diff_eq!(kernel.density(0.0), 0.408_248_290_463_863_1);
+        assert_abs_diff_eq!(kernel.density(f64::SQRT_6 / 2.0), 0.204_124_145_231_931_54);
+        assert_abs_diff_eq!(kernel.density(-f64::SQRT_6), 0.0);
+        assert_abs_diff_eq!(kernel.density(10.0), 0.0);
+    }
+
+    #[test]
+    fn sample_ok() {
+        assert_standardized(&Triangular::default());
+    }
+}
@@ -0,0 +1,104 @@
+use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::FromPrimitive;
+
+use crate::{
+    constants::{ConstThirtyFiveThirtySeconds, ConstThree},
+    kernel::{continuous::sample_uniform_median, Density, Kernel, Sample},
+    traits::ops::{Arithmetic, Neg},
+};
+
+/// [Standardized][1] triweight kernel, over (-3, +3) range.
+///
+/// [1]: https://en.wikipedia.org/wiki/Kernel_(statistics)#Kernel_functions_in_common_use
+#[derive(Copy, Clone, Debug)]
+pub struct Triweight<T> {
+    location: T,
+    std: T,
+}
+
+impl<T> Density for Triweight<T>
+where
+    T: Arithmetic
+        + Copy
+        + PartialOrd
+        + Neg
+        + num_traits::One
+        + num_traits::Zero
+        + ConstThree
+        + ConstThirtyFiveThirtySeconds,
+{
+    type Param = T;
+    type Output = T;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        // Scale to `-1..1`:
+        let normalized = (at - self.location) / self.std / T::THREE;
+        if (-T::one()..=T::one()).contains(&normalized) {
+            let base = T::one() - normalized * normalized;
+            T::THIRTY_FIVE_THIRTY_SECONDS / T::THREE * base * base * base / self.std
+        } else {
+            T::zero()
+        }
+    }
+}
+
+impl<T> Sample for Triweight<T>
+where
+    T: Copy + Arithmetic + FromPrimitive,
+{
+    type Param = T;
+
+    /// Generate a sample from the scaled `Beta(4, 4)` distribution.
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        let normalized = sample_uniform_median::<7>(rng).mul_add(2.0, -1.0);
+        self.location + self.std * T::from_f64(normalized * 3.0).unwrap()
+    }
+}
+
+impl<T> Kernel for Triweight<T>
+where
+    Self: Density<Param = T, Output = T> + Sample<Param = T>,
+    T: PartialOrd + num_traits::Zero,
+{
+    type Param = T;
+
+    fn new(location: T, std: T) -> Self {
+        assert!(std > T::zero());
+        Self { location, std }
+    }
+}
+
+impl<T> Default for Triweight<T>
+where
+    T: num_traits::Zero + num_traits::One,
+{
+    fn default() -> Self {
+        Self {
+            location: T::zero(),
+            std: T::one(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::kernel::continuous::tests::assert_standardized;
+
+    #[test]
+    fn density_ok() {
+        let kernel = Triweight::<f64>::default();
+        assert_abs_diff_eq!(kernel.density(0.0), 0.364_583_333_333_333_3);
+        assert_abs_diff_eq!(kernel.density(3.0), 0.0);
+        assert_abs_diff_eq!(kernel.density(-10.0), 0.0);
+    }
+
+    #[test]
+    fn sample_ok() {
+        assert_standardized(&Triweight::default());
+    }
+}
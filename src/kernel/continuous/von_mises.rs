@@ -0,0 +1,278 @@
+use core::{
+    f64::consts::{PI, TAU},
+    ops::RangeInclusive,
+};
+
+use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{FromPrimitive, ToPrimitive};
+
+use crate::{
+    kernel::{Density, Kernel, Sample},
+    traits::ops::Arithmetic,
+    Error,
+};
+
+/// [Von Mises][1] kernel, also known as «circular normal distribution».
+///
+/// The kernel wraps around its period, which makes it suitable for periodic parameters,
+/// such as angles, phases or time of day. Bounded by the search range,
+/// the kernel takes the range width as the period. Otherwise, the period is `2π`.
+///
+/// [1]: https://en.wikipedia.org/wiki/Von_Mises_distribution
+#[derive(Copy, Clone, Debug)]
+pub struct VonMises<T> {
+    location: T,
+
+    /// Concentration `κ`, which is roughly the reciprocal of the angular variance.
+    concentration: f64,
+
+    /// Start of the period.
+    min: T,
+
+    period: T,
+}
+
+impl<T> VonMises<T>
+where
+    T: Copy + Arithmetic + ToPrimitive,
+{
+    /// Scale the parameter to the angle in radians.
+    fn angle(&self, parameter: T) -> f64 {
+        TAU * (parameter / self.period).to_f64().unwrap()
+    }
+}
+
+impl<T> Density for VonMises<T>
+where
+    T: Copy + Arithmetic + ToPrimitive + FromPrimitive,
+{
+    type Param = T;
+    type Output = T;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        let angle = self.angle(at - self.location);
+
+        // Scale the exponent and the Bessel function down by `exp(-κ)` to avoid overflowing:
+        let density =
+            (self.concentration * (angle.cos() - 1.0)).exp() / bessel_i0_scaled(self.concentration);
+
+        // Normalize over the period:
+        T::from_f64(density).unwrap() / self.period
+    }
+}
+
+impl<T> Sample for VonMises<T>
+where
+    T: Copy + Arithmetic + ToPrimitive + FromPrimitive,
+{
+    type Param = T;
+
+    /// Generate a sample using the [Best–Fisher algorithm][1], and wrap it into the period.
+    ///
+    /// [1]: https://doi.org/10.2307/2346732
+    #[allow(clippy::many_single_char_names)]
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        let kappa = self.concentration;
+        let angle = if kappa < 1e-8 {
+            // The distribution is almost uniform:
+            PI * rng.f64().mul_add(2.0, -1.0)
+        } else if kappa > 1e6 {
+            // The distribution is almost normal, use the Box–Muller transform:
+            (-2.0 * (1.0 - rng.f64()).ln()).sqrt() * (TAU * rng.f64()).cos() / kappa.sqrt()
+        } else {
+            let s = best_fisher_s(kappa);
+            let w = loop {
+                let z = (PI * rng.f64()).cos();
+                let w = s.mul_add(z, 1.0) / (s + z);
+                let y = kappa * (s - w);
+                let v = rng.f64();
+                if y.mul_add(2.0 - y, -v) >= 0.0 || (y / v).ln() + 1.0 - y >= 0.0 {
+                    break w;
+                }
+            };
+            if rng.bool() {
+                w.acos()
+            } else {
+                -w.acos()
+            }
+        };
+
+        // Scale back to the parameter space, and wrap around:
+        let period = self.period.to_f64().unwrap();
+        let offset = (angle / TAU).mul_add(period, (self.location - self.min).to_f64().unwrap());
+        self.min + T::from_f64(num_traits::Euclid::rem_euclid(&offset, &period)).unwrap()
+    }
+}
+
+/// Calculate the Best–Fisher parameter `s = (1 + ρ²) / 2ρ` of the wrapped Cauchy envelope.
+fn best_fisher_s(kappa: f64) -> f64 {
+    if kappa < 1e-5 {
+        // `r - √(2r)` loses precision for the small concentrations, hence the Taylor expansion
+        // `1/κ + κ - κ³ + …`, whose cubic term is already below the `f64` precision:
+        1.0 / kappa + kappa
+    } else {
+        let r = 1.0 + 4.0_f64.mul_add(kappa * kappa, 1.0).sqrt();
+        let rho = (r - (2.0 * r).sqrt()) / (2.0 * kappa);
+        rho.mul_add(rho, 1.0) / (2.0 * rho)
+    }
+}
+
+impl<T> Kernel for VonMises<T>
+where
+    Self: Density<Param = T, Output = T> + Sample<Param = T>,
+    T: Copy + Arithmetic + PartialOrd + ToPrimitive + FromPrimitive + num_traits::Zero,
+{
+    type Param = T;
+
+    /// Construct the kernel over the `-π..=π` period.
+    fn new(location: T, std: T) -> Self {
+        Self::new_bounded(
+            location,
+            std,
+            T::from_f64(-PI).unwrap()..=T::from_f64(PI).unwrap(),
+        )
+    }
+
+    fn new_bounded(location: T, std: T, bounds: RangeInclusive<T>) -> Self {
+        assert!(std > T::zero());
+        let (min, max) = bounds.into_inner();
+        let period = max - min;
+        assert!(period > T::zero());
+        let std_angle = TAU * (std / period).to_f64().unwrap();
+        Self {
+            location,
+            concentration: 1.0 / (std_angle * std_angle),
+            min,
+            period,
+        }
+    }
+
+    fn try_new_bounded(location: T, std: T, bounds: RangeInclusive<T>) -> Result<Self, Error> {
+        if std > T::zero() && bounds.start() < bounds.end() {
+            Ok(Self::new_bounded(location, std, bounds))
+        } else {
+            Err(Error::InvalidBandwidth)
+        }
+    }
+}
+
+/// Calculate the [modified Bessel function][1] `I₀(x)`, scaled by `exp(-x)`, for `x ≥ 0`.
+///
+/// This uses the polynomial approximations from Abramowitz and Stegun, 9.8.1 and 9.8.2,
+/// whose relative error is within `2e-7`.
+///
+/// [1]: https://en.wikipedia.org/wiki/Bessel_function#Modified_Bessel_functions:_I%CE%B1,_K%CE%B1
+#[allow(clippy::unreadable_literal)]
+fn bessel_i0_scaled(x: f64) -> f64 {
+    let t = x / 3.75;
+    if t <= 1.0 {
+        let t2 = t * t;
+        let i0 = [
+            0.0045813, 0.0360768, 0.2659732, 1.2067492, 3.0899424, 3.5156229, 1.0,
+        ]
+        .into_iter()
+        .fold(0.0_f64, |sum, coefficient| sum.mul_add(t2, coefficient));
+        i0 * (-x).exp()
+    } else {
+        let t = 1.0 / t;
+        let scaled = [
+            0.00392377,
+            -0.01647633,
+            0.02635537,
+            -0.02057706,
+            0.00916281,
+            -0.00157565,
+            0.00225319,
+            0.01328592,
+            0.39894228,
+        ]
+        .into_iter()
+        .fold(0.0_f64, |sum, coefficient| sum.mul_add(t, coefficient));
+        scaled / x.sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::SQRT_2;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn density_ok() {
+        let kernel = VonMises::new(0.0, 1.0);
+        assert_abs_diff_eq!(
+            kernel.density(0.0),
+            0.341_710_488_623_463_16,
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(kernel.density(PI), 0.046_245_485_762_777_7, epsilon = 1e-6);
+        assert_abs_diff_eq!(kernel.density(-PI), kernel.density(PI));
+    }
+
+    #[test]
+    fn density_wraps_around_ok() {
+        let kernel = VonMises::new_bounded(350.0, 10.0, 0.0..=360.0);
+        assert_abs_diff_eq!(kernel.density(0.0), kernel.density(340.0), epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.density(0.0), kernel.density(360.0), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn density_normalized_ok() {
+        let kernel = VonMises::new_bounded(350.0, 10.0, 0.0..=360.0);
+        let integral = (0..3600)
+            .map(|i| kernel.density(f64::from(i) / 10.0))
+            .sum::<f64>()
+            / 10.0;
+        assert_abs_diff_eq!(integral, 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn sample_ok() {
+        let kernel = VonMises::new_bounded(350.0, 10.0, 0.0..=360.0);
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..1000 {
+            assert!((0.0..360.0).contains(&kernel.sample(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn bessel_i0_scaled_ok() {
+        assert_abs_diff_eq!(bessel_i0_scaled(0.0), 1.0);
+        assert_abs_diff_eq!(
+            bessel_i0_scaled(1.0),
+            0.465_759_607_593_640_4,
+            epsilon = 1e-7
+        );
+        assert_abs_diff_eq!(
+            bessel_i0_scaled(10.0),
+            0.127_833_337_163_428_6,
+            epsilon = 1e-7
+        );
+    }
+
+    #[test]
+    fn best_fisher_s_ok() {
+        // The Taylor expansion is continuous with the exact formula at the threshold,
+        // up to the precision loss of the latter:
+        let (below, above) = (best_fisher_s(1e-5 - 1e-12), best_fisher_s(1e-5));
+        assert_abs_diff_eq!(below / above, 1.0 + 1e-7, epsilon = 1e-6);
+
+        // Below the threshold, it matches the exact value `1/κ + κ - κ³ + …`:
+        assert_abs_diff_eq!(best_fisher_s(1e-6), 1e6 + 1e-6, epsilon = 1e-9);
+        assert_abs_diff_eq!(best_fisher_s(0.5), 1.0 + SQRT_2, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn try_new_bounded_empty_range_fails() {
+        assert!(VonMises::try_new_bounded(0.0, 1.0, 0.0..=360.0).is_ok());
+        assert!(matches!(
+            VonMises::try_new_bounded(0.0, 1.0, 0.0..=0.0),
+            Err(Error::InvalidBandwidth)
+        ));
+    }
+}
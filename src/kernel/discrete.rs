@@ -1,5 +1,50 @@
 //! Discrete kernels.
 
+mod beta_binomial;
 mod binomial;
+mod categorical;
+mod discrete_gaussian;
+mod geometric;
+mod uniform_neighbourhood;
 
-pub use self::binomial::Binomial;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{Bounded, NumCast, ToPrimitive};
+
+pub use self::{
+    beta_binomial::BetaBinomial,
+    binomial::Binomial,
+    categorical::Categorical,
+    discrete_gaussian::DiscreteGaussian,
+    geometric::Geometric,
+    uniform_neighbourhood::UniformNeighbourhood,
+};
+
+/// Round the number to the nearest integer parameter, saturating at the type bounds.
+fn round_saturating<P: Bounded + NumCast + ToPrimitive>(x: f64) -> P {
+    let min = P::min_value().to_f64().unwrap();
+    let max = P::max_value().to_f64().unwrap();
+    let x = x.round();
+    if x <= min {
+        P::min_value()
+    } else if x >= max {
+        P::max_value()
+    } else {
+        P::from(x).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_saturating_ok() {
+        assert_eq!(round_saturating::<i8>(-1000.0), i8::MIN);
+        assert_eq!(round_saturating::<i8>(-1.6), -2);
+        assert_eq!(round_saturating::<u8>(-1.0), 0);
+        assert_eq!(round_saturating::<u8>(254.4), 254);
+        assert_eq!(round_saturating::<u8>(1000.0), u8::MAX);
+        assert_eq!(round_saturating::<u128>(f64::MAX), u128::MAX);
+    }
+}
@@ -0,0 +1,397 @@
+use core::{
+    iter,
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+};
+
+use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{FromPrimitive, One, PrimInt, ToPrimitive, Zero};
+
+use crate::{
+    iter::log_sum_exp,
+    kernel::{discrete::round_saturating, Kernel},
+    traits::ops::{Arithmetic, Exp, Ln},
+    Density,
+    Error,
+    Sample,
+};
+
+/// Discrete kernel based on the [beta-binomial distribution][1], shifted by the offset.
+///
+/// It is the binomial distribution, whose success rate follows the beta distribution.
+/// The overdispersion `ρ = 1 / (α + β + 1)` controls how much heavier its tails are compared to
+/// [`super::Binomial`]. Note that sampling takes `O(σ)` steps on average.
+///
+/// [1]: https://en.wikipedia.org/wiki/Beta-binomial_distribution
+#[derive(Copy, Clone, Debug)]
+pub struct BetaBinomial<P, D> {
+    /// Number of trials, always a whole number.
+    n: f64,
+
+    alpha: f64,
+    beta: f64,
+
+    /// Start of the support, which corresponds to zero successes.
+    offset: P,
+
+    _density: PhantomData<D>,
+}
+
+impl<P: PrimInt, D> BetaBinomial<P, D> {
+    /// Overdispersion, which [`Kernel::new`] uses.
+    pub const DEFAULT_OVERDISPERSION: f64 = 0.1;
+
+    /// Construct the centred kernel with the specified overdispersion `0 < ρ < 1`.
+    ///
+    /// Just like with [`super::Binomial`], near the bounds of the parameter type,
+    /// the support gets shifted to fit into the type, and `α` and `β` get adjusted to keep the mean
+    /// at the location. Too wide a kernel gets narrowed down to the whole range of the type.
+    ///
+    /// # Panics
+    ///
+    /// This function panics, if the standard deviation is not positive,
+    /// or the overdispersion is out of its range.
+    pub fn with_overdispersion(location: P, std: P, overdispersion: f64) -> Self {
+        assert!(std > P::zero());
+        assert!(overdispersion > 0.0 && overdispersion < 1.0);
+        Self::with_real_std(location, std.to_f64().unwrap(), overdispersion)
+    }
+
+    /// Construct the centred kernel with the real-valued standard deviation.
+    fn with_real_std(location: P, std: f64, overdispersion: f64) -> Self {
+        // Solving `σ² = n/4 * (1 + (n - 1)ρ)` for `n`, and rounding it to an even number:
+        let variance = std.powi(2);
+        let n = (16.0 * overdispersion)
+            .mul_add(variance, (1.0 - overdispersion).powi(2))
+            .sqrt()
+            - (1.0 - overdispersion);
+        let n = 2.0 * (n / overdispersion / 4.0).round().max(1.0);
+
+        let alpha_beta = 1.0 / overdispersion - 1.0;
+        let location = location.to_f64().unwrap();
+        let min = P::min_value().to_f64().unwrap();
+        let max = P::max_value().to_f64().unwrap();
+
+        // The support `offset..=offset + n` must fit into the parameter type:
+        let n = n.min(max - min);
+        let offset = (location - n / 2.0).max(min).min(max - n);
+
+        // Keep the mean at the location, but away from the support bounds:
+        let mean = (location - offset).max(0.5).min(n - 0.5);
+        let alpha = alpha_beta * mean / n;
+
+        Self {
+            n,
+            alpha,
+            beta: alpha_beta - alpha,
+            offset: round_saturating(offset),
+            _density: PhantomData,
+        }
+    }
+
+    /// Probability mass function of the number of successes.
+    fn pmf(&self, k: f64) -> f64 {
+        libm::exp(self.log_pmf(k))
+    }
+
+    /// Natural logarithm of the probability mass function of the number of successes.
+    fn log_pmf(&self, k: f64) -> f64 {
+        if k < 0.0 || k > self.n {
+            return f64::NEG_INFINITY;
+        }
+        let log_binomial =
+            libm::lgamma(self.n + 1.0) - libm::lgamma(k + 1.0) - libm::lgamma(self.n - k + 1.0);
+        log_binomial + ln_beta(k + self.alpha, self.n - k + self.beta)
+            - ln_beta(self.alpha, self.beta)
+    }
+}
+
+/// Natural logarithm of the [beta function][1].
+///
+/// [1]: https://en.wikipedia.org/wiki/Beta_function
+fn ln_beta(a: f64, b: f64) -> f64 {
+    libm::lgamma(a) + libm::lgamma(b) - libm::lgamma(a + b)
+}
+
+impl<P, D> Density for BetaBinomial<P, D>
+where
+    P: PrimInt,
+    D: FromPrimitive,
+{
+    type Param = P;
+    type Output = D;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        let k = at.to_f64().unwrap() - self.offset.to_f64().unwrap();
+        D::from_f64(self.pmf(k)).unwrap()
+    }
+
+    fn log_density(&self, at: Self::Param) -> Self::Output
+    where
+        Self::Output: Ln,
+    {
+        let k = at.to_f64().unwrap() - self.offset.to_f64().unwrap();
+        D::from_f64(self.log_pmf(k)).unwrap()
+    }
+
+    /// Sum up the probability masses within `[start, end)` in `O(min(end - start, n))` operations.
+    fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+    where
+        Self::Param: Copy + ToPrimitive + FromPrimitive,
+        Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        let Range { start, end } = interval;
+        if start >= end {
+            return D::from_f64(f64::NEG_INFINITY).unwrap();
+        }
+        let offset = self.offset.to_f64().unwrap();
+        let first = (start.to_f64().unwrap() - offset).max(0.0);
+        let last = ((end - P::one()).to_f64().unwrap() - offset).min(self.n);
+        let log_sum = log_sum_exp(
+            iter::successors(Some(first), |k| Some(k + 1.0))
+                .take_while(|k| *k <= last)
+                .map(|k| self.log_pmf(k)),
+        );
+        D::from_f64(log_sum).unwrap()
+    }
+}
+
+impl<P: PrimInt, D> Sample for BetaBinomial<P, D> {
+    type Param = P;
+
+    /// Generate a sample using the inverse cumulative distribution function.
+    ///
+    /// The walk starts at the mean, where the probability mass does not underflow,
+    /// and expands to whichever side has the larger next mass. The probabilities are accumulated
+    /// via the recurrence `pmf(k + 1) = pmf(k) * (n - k) / (k + 1) * (k + α) / (n - k - 1 + β)`,
+    /// so it takes `O(σ)` steps on average.
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        let cdf = rng.f64();
+        let mean = (self.n * self.alpha / (self.alpha + self.beta)).round();
+        let (mut lower, mut upper) = (mean, mean);
+        let (mut lower_pmf, mut upper_pmf) = (self.pmf(mean), self.pmf(mean));
+        let (mut k, mut acc) = (mean, lower_pmf);
+        while acc < cdf && (lower > 0.0 || upper < self.n) {
+            let next_lower_pmf = if lower > 0.0 {
+                lower_pmf * lower / (self.n - lower + 1.0) * (self.n - lower + self.beta)
+                    / (lower - 1.0 + self.alpha)
+            } else {
+                0.0
+            };
+            let next_upper_pmf = if upper < self.n {
+                upper_pmf * (self.n - upper) / (upper + 1.0) * (upper + self.alpha)
+                    / (self.n - upper - 1.0 + self.beta)
+            } else {
+                0.0
+            };
+            if next_lower_pmf <= 0.0 && next_upper_pmf <= 0.0 {
+                // The rest of the support is negligible:
+                break;
+            }
+            if next_upper_pmf >= next_lower_pmf {
+                upper += 1.0;
+                upper_pmf = next_upper_pmf;
+                acc += upper_pmf;
+                k = upper;
+            } else {
+                lower -= 1.0;
+                lower_pmf = next_lower_pmf;
+                acc += lower_pmf;
+                k = lower;
+            }
+        }
+        round_saturating(self.offset.to_f64().unwrap() + k)
+    }
+}
+
+impl<P, D> Kernel for BetaBinomial<P, D>
+where
+    Self: Density<Param = P, Output = D> + Sample<Param = P>,
+    P: PrimInt,
+{
+    type Param = P;
+
+    fn new(location: P, std: P) -> Self {
+        Self::with_overdispersion(location, std, Self::DEFAULT_OVERDISPERSION)
+    }
+
+    /// Construct the kernel with the real-valued bandwidth as is, ignoring the bounds.
+    fn try_new_scaled(
+        location: P,
+        std: f64,
+        bounds: Option<RangeInclusive<P>>,
+    ) -> Result<Self, Error> {
+        let _ = bounds;
+        if std.is_finite() && std > 0.0 {
+            Ok(Self::with_real_std(
+                location,
+                std,
+                Self::DEFAULT_OVERDISPERSION,
+            ))
+        } else {
+            Err(Error::InvalidBandwidth)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn new_ok() {
+        let kernel = BetaBinomial::<_, f64>::with_overdispersion(-10, 2, 0.2);
+        assert_abs_diff_eq!(kernel.n, 8.0);
+        assert_abs_diff_eq!(kernel.alpha, 2.0);
+        assert_abs_diff_eq!(kernel.beta, 2.0);
+        assert_eq!(kernel.offset, -14);
+    }
+
+    #[test]
+    fn try_new_scaled_ok() {
+        let kernel = BetaBinomial::<i32, f64>::try_new_scaled(0, 2.9, None).unwrap();
+        assert_abs_diff_eq!(kernel.n, 14.0);
+        assert!(BetaBinomial::<i32, f64>::try_new_scaled(0, f64::INFINITY, None).is_err());
+    }
+
+    #[test]
+    fn new_unsigned_ok() {
+        let kernel = BetaBinomial::<u8, f64>::with_overdispersion(1, 2, 0.2);
+        assert_eq!(kernel.offset, 0);
+        assert_abs_diff_eq!(kernel.n * kernel.alpha / (kernel.alpha + kernel.beta), 1.0);
+    }
+
+    #[test]
+    fn new_near_max_ok() {
+        let kernel = BetaBinomial::<u8, f64>::with_overdispersion(254, 2, 0.2);
+        assert_eq!(kernel.offset, 247);
+        assert_abs_diff_eq!(kernel.n * kernel.alpha / (kernel.alpha + kernel.beta), 7.0);
+        assert_abs_diff_eq!(
+            (247..=255).map(|at| kernel.density(at)).sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+
+        // The samples stay within the support, instead of piling up at the maximum:
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..100 {
+            assert!(kernel.sample(&mut rng) >= 247);
+        }
+
+        // Too wide a kernel gets narrowed down to the whole type range:
+        let kernel = BetaBinomial::<u8, f64>::with_overdispersion(10, 200, 0.2);
+        assert_eq!(kernel.offset, 0);
+        assert_abs_diff_eq!(kernel.n, 255.0);
+    }
+
+    #[test]
+    fn density_ok() {
+        let kernel = BetaBinomial::<_, f64>::with_overdispersion(-10, 2, 0.2);
+        assert_abs_diff_eq!(kernel.density(-15), 0.0);
+        assert_abs_diff_eq!(
+            kernel.density(-10),
+            0.151_515_151_515_151_5,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(kernel.density(-12), kernel.density(-8), epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            (-14..=-6).map(|at| kernel.density(at)).sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn log_density_ok() {
+        let kernel = BetaBinomial::<_, f64>::with_overdispersion(-10, 2, 0.2);
+        assert_abs_diff_eq!(
+            kernel.log_density(-10),
+            libm::log(kernel.density(-10)),
+            epsilon = 1e-12
+        );
+
+        // The density underflows in the tail, but the log-density does not:
+        let kernel = BetaBinomial::<_, f64>::with_overdispersion(0, 100, 0.001);
+        assert_eq!(kernel.offset, -2922);
+        assert_abs_diff_eq!(kernel.density(-2922), 0.0);
+        assert_abs_diff_eq!(
+            kernel.log_density(-2922),
+            -1_096.042_163_297_831,
+            epsilon = 1e-6
+        );
+        assert_eq!(kernel.log_density(-2923), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn log_mass_ok() {
+        let kernel = BetaBinomial::<_, f64>::new(64, 1);
+        assert_abs_diff_eq!(kernel.log_mass(48..80), 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            kernel.log_mass(62..64),
+            libm::log(kernel.density(62) + kernel.density(63)),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            (0..10)
+                .map(|i| kernel.log_mass(50 + i * 3..53 + i * 3).exp())
+                .sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+        assert_eq!(kernel.log_mass(67..80), f64::NEG_INFINITY);
+        assert_eq!(kernel.log_mass(64..64), f64::NEG_INFINITY);
+
+        // The mass underflows, but the log-mass does not:
+        let kernel = BetaBinomial::<_, f64>::with_overdispersion(0, 100, 0.001);
+        assert_abs_diff_eq!(
+            kernel.log_mass(-3000..-2921),
+            kernel.log_density(-2922),
+            epsilon = 1e-6
+        );
+
+        let kernel = BetaBinomial::<u8, f64>::with_overdispersion(254, 2, 0.2);
+        assert_abs_diff_eq!(
+            kernel.log_mass(0..u8::MAX).exp() + kernel.density(u8::MAX),
+            1.0,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn sample_ok() {
+        let kernel = BetaBinomial::<i32, f64>::with_overdispersion(-10, 2, 0.2);
+        let mut rng = Rng::with_seed(42);
+        let samples: Vec<i32> = (0..10_000).map(|_| kernel.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|sample| (-14..=-6).contains(sample)));
+        let mean = f64::from(samples.iter().sum::<i32>()) / 10_000.0;
+        assert_abs_diff_eq!(mean, -10.0, epsilon = 0.1);
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn sample_wide_ok() {
+        // The mass at the start of the support underflows, yet the samples must not pile up:
+        let kernel = BetaBinomial::<i32, f64>::with_overdispersion(0, 100, 0.001);
+        let mut rng = Rng::with_seed(42);
+        let samples: Vec<f64> = (0..10_000)
+            .map(|_| f64::from(kernel.sample(&mut rng)))
+            .collect();
+        let mean = samples.iter().sum::<f64>() / 10_000.0;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample - mean).powi(2))
+            .sum::<f64>()
+            / 10_000.0;
+        assert_abs_diff_eq!(mean, 0.0, epsilon = 5.0);
+        assert_abs_diff_eq!(variance.sqrt(), 100.0, epsilon = 5.0);
+    }
+}
@@ -1,23 +1,44 @@
-use std::{fmt::Debug, iter::Sum};
+use core::{
+    f64::consts::{SQRT_2, TAU},
+    fmt::Debug,
+    ops::{Range, RangeInclusive},
+};
 
 use fastrand::Rng;
-use num_traits::{Float, FromPrimitive, One, Zero};
+use num_traits::{
+    Bounded,
+    CheckedAdd,
+    Float,
+    FromPrimitive,
+    NumCast,
+    One,
+    SaturatingAdd,
+    SaturatingMul,
+    SaturatingSub,
+    ToPrimitive,
+    Zero,
+};
 
 use crate::{
-    iter::{range_inclusive, range_step_from},
-    kernel::Kernel,
-    traits::ops::{Additive, Arithmetic, Multiplicative},
+    iter::{log_sum_exp, range_step_from},
+    kernel::{continuous::standard_normal_quantile, Cdf, Kernel, Quantile},
+    traits::ops::{Additive, Arithmetic, Exp, Ln},
     Density,
+    Error,
     Sample,
 };
 
-/// Discrete kernel function based on the [binomial distribution][1].
+/// Discrete kernel function based on the [binomial distribution][1], shifted by the offset.
 ///
+/// The support is `offset..=offset + n`, so that the kernel works with negative parameters, too.
 /// The probability mass function is normalized by dividing on the standard deviation.
 ///
-/// Note that [`Binomial::density`] is a `O(at)` operation, so it's pretty slow.
+/// The probability mass function is calculated in constant time via the log-gamma function.
+/// Sampling takes constant time as well: small variances are sampled exactly
+/// by [`Quantile::quantile`], while large ones follow the [normal approximation][2].
 ///
 /// [1]: https://en.wikipedia.org/wiki/Binomial_distribution
+/// [2]: https://en.wikipedia.org/wiki/Binomial_distribution#Normal_approximation
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct Binomial<P, D> {
     /// Number of independent experiments (distribution parameter).
@@ -25,37 +46,54 @@ pub struct Binomial<P, D> {
 
     /// Experiment success rate (distribution parameter).
     pub p: D,
+
+    /// Start of the support, which corresponds to zero successes.
+    pub offset: P,
 }
 
 impl<P, D> Binomial<P, D> {
+    /// Variance, starting from which the samples follow the normal approximation.
+    ///
+    /// Below it, the samples are exact, see [`Binomial::inverse_cdf`].
+    const NORMAL_APPROXIMATION_VARIANCE: f64 = 100.0;
+
     /// Probability mass function.
     fn pmf(&self, at: P) -> D
     where
         P: Copy + Into<D> + PartialOrd + Zero + One + Additive,
-        D: Float + Sum,
+        D: Float,
     {
-        if self.p == D::one() {
-            // The only possible outcome is `at == n`:
-            if at == self.n { D::one() } else { D::zero() }
-        } else if self.p == D::zero() {
-            // The only possible outcome is `at == 0`:
-            if at == P::zero() { D::one() } else { D::zero() }
-        } else if at <= self.n {
-            // lg(n choose k) = Σ ln(n + 1 - i) - ln(i)
-            let log_binomial: D = range_inclusive(P::one(), at)
-                .map(|i| (self.n - at + i).into().ln() - i.into().ln())
-                .sum();
-            let log_pmf = log_binomial
-                + at.into() * self.p.ln()
-                + (self.n - at).into() * (D::one() - self.p).ln();
-            log_pmf.exp()
-        } else {
-            // It is impossible to have more successes than experiments, hence the zero.
-            D::zero()
+        self.log_pmf(at).exp()
+    }
+
+    /// Natural logarithm of the probability mass function.
+    fn log_pmf(&self, at: P) -> D
+    where
+        P: Copy + Into<D> + PartialOrd + Zero + One + Additive,
+        D: Float,
+    {
+        if at < self.offset {
+            // It is impossible to have fewer than zero successes:
+            return D::neg_infinity();
         }
+        self.unshifted_log_pmf(at - self.offset)
+    }
+
+    /// Natural logarithm of the probability mass function of the number of successes.
+    fn unshifted_log_pmf(&self, at: P) -> D
+    where
+        P: Copy + Into<D>,
+        D: Float,
+    {
+        D::from(binomial_log_pmf(
+            self.n.into().to_f64().unwrap(),
+            self.p.to_f64().unwrap(),
+            at.into().to_f64().unwrap(),
+        ))
+        .unwrap()
     }
 
-    /// Standard deviation: √(p * (1 - p) / n).
+    /// Standard deviation: √(n * p * (1 - p)).
     fn std(&self) -> D
     where
         P: Copy + Into<D>,
@@ -64,26 +102,221 @@ impl<P, D> Binomial<P, D> {
         (self.n.into() * self.p * (D::one() - self.p)).sqrt()
     }
 
+    /// Find the smallest number of successes, at which the CDF reaches the specified value.
+    ///
+    /// Note that the result is not shifted by the offset.
+    ///
+    /// The walk starts at the [Cornish–Fisher][1] approximation of the quantile, where the CDF
+    /// is calculated exactly, and corrects it via the recurrence
+    /// `pmf(k + 1) = pmf(k) * (n - k) / (k + 1) * p / (1 - p)`. The approximation is off
+    /// by a few points at most, so the walk takes a constant number of steps on average.
+    /// The probabilities are accumulated in `f64`, so that they do not underflow for `f32`.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Cornish%E2%80%93Fisher_expansion
     fn inverse_cdf(&self, cdf: D) -> P
     where
-        P: Copy + Into<D> + One + Zero + PartialOrd + Additive,
-        D: Copy + Float + Sum,
+        P: Copy + Into<D> + NumCast + Zero,
+        D: Float,
     {
-        range_step_from(P::zero(), P::one())
-            .scan(D::zero(), |acc, at| {
-                *acc = *acc + self.pmf(at);
-                Some((at, *acc))
-            })
-            .find(|(_, acc)| *acc >= cdf)
-            .expect("there should be a next sample")
-            .0
+        if self.p == D::zero() || cdf <= D::zero() {
+            return P::zero();
+        }
+        if self.p == D::one() || cdf >= D::one() {
+            return self.n;
+        }
+        let n = self.n.into().to_f64().unwrap();
+        let p = self.p.to_f64().unwrap();
+        let cdf = cdf.to_f64().unwrap();
+
+        // Normal approximation with the continuity and skewness corrections:
+        let normalized = standard_normal_quantile(cdf);
+        let skewness_correction = (1.0 - 2.0 * p) * normalized.mul_add(normalized, -1.0) / 6.0;
+        let guess = (n * p * (1.0 - p))
+            .sqrt()
+            .mul_add(normalized, n * p + skewness_correction - 0.5);
+
+        let odds = p / (1.0 - p);
+        let mut k = guess.round().clamp(0.0, n);
+        let mut pmf = libm::exp(binomial_log_pmf(n, p, k));
+        let mut acc = binomial_cdf(n, p, k);
+        if acc >= cdf {
+            // Step down while the CDF at the previous point still reaches the value:
+            while k > 0.0 && pmf > 0.0 && acc - pmf >= cdf {
+                acc -= pmf;
+                pmf *= k / (n - k + 1.0) / odds;
+                k -= 1.0;
+            }
+        } else {
+            while k < n && pmf > 0.0 && acc < cdf {
+                pmf *= (n - k) / (k + 1.0) * odds;
+                acc += pmf;
+                k += 1.0;
+            }
+        }
+        P::from(k).unwrap()
+    }
+}
+
+/// Natural logarithm of the probability mass function of `k` successes in `n` experiments.
+#[allow(clippy::float_cmp, clippy::many_single_char_names)]
+fn binomial_log_pmf(n: f64, p: f64, k: f64) -> f64 {
+    if k < 0.0 || k > n {
+        // It is impossible to have fewer than zero successes or more successes than experiments:
+        f64::NEG_INFINITY
+    } else if p == 1.0 {
+        // The only possible outcome is `k == n`:
+        if k == n {
+            0.0
+        } else {
+            f64::NEG_INFINITY
+        }
+    } else if p == 0.0 {
+        // The only possible outcome is `k == 0`:
+        if k == 0.0 {
+            0.0
+        } else {
+            f64::NEG_INFINITY
+        }
+    } else if k == 0.0 {
+        n * libm::log1p(-p)
+    } else if k == n {
+        n * libm::log(p)
+    } else {
+        // Loader's saddle point expansion, which does not lose precision for large `n`,
+        // unlike the difference of the log-gamma functions:
+        let (m, q) = (n - k, 1.0 - p);
+        0.5f64.mul_add(
+            libm::log(n / (TAU * k * m)),
+            stirling_error(n) - stirling_error(k) - stirling_error(m),
+        ) - deviance(k, n * p)
+            - deviance(m, n * q)
+    }
+}
+
+/// Error of Stirling's approximation: `ln x! - (x + ½) ln x + x - ½ ln 2π`.
+fn stirling_error(x: f64) -> f64 {
+    if x < 15.0 {
+        libm::lgamma(x + 1.0) - (x + 0.5).mul_add(libm::log(x), -x) - 0.5 * libm::log(TAU)
+    } else {
+        // Asymptotic series, which is precise to the machine epsilon starting from here:
+        let x2 = x * x;
+        (1.0 / 12.0 - (1.0 / 360.0 - (1.0 / 1260.0 - 1.0 / 1680.0 / x2) / x2) / x2) / x
+    }
+}
+
+/// Deviance term `x ln(x / μ) + μ - x`, calculated without the cancellation when `x ≈ μ`.
+#[allow(clippy::float_cmp)]
+fn deviance(x: f64, mean: f64) -> f64 {
+    if (x - mean).abs() >= 0.1 * (x + mean) {
+        x.mul_add(libm::log(x / mean), mean - x)
+    } else {
+        // Series in `v = (x - μ) / (x + μ)`:
+        let v = (x - mean) / (x + mean);
+        let v2 = v * v;
+        let mut sum = (x - mean) * v;
+        let (mut term, mut divisor) = (2.0 * x * v, 1.0);
+        loop {
+            term *= v2;
+            divisor += 2.0;
+            let next = sum + term / divisor;
+            if next == sum {
+                return sum;
+            }
+            sum = next;
+        }
+    }
+}
+
+/// Cumulative distribution function of the number of successes in `n` experiments.
+///
+/// It is calculated in constant time via the [regularized incomplete beta function][1]:
+/// `cdf(k) = I(1 - p; n - k, k + 1)`. Should the latter not converge, the CDF falls back
+/// to the [normal approximation][2].
+///
+/// [1]: https://en.wikipedia.org/wiki/Binomial_distribution#Cumulative_distribution_function
+/// [2]: https://en.wikipedia.org/wiki/Binomial_distribution#Normal_approximation
+fn binomial_cdf(n: f64, p: f64, k: f64) -> f64 {
+    if k < 0.0 {
+        0.0
+    } else if k >= n {
+        1.0
+    } else {
+        regularized_incomplete_beta(1.0 - p, n - k, k + 1.0).unwrap_or_else(|| {
+            let normalized = (k + 0.5 - n * p) / (n * p * (1.0 - p)).sqrt();
+            0.5 * libm::erfc(-normalized / SQRT_2)
+        })
+    }
+}
+
+/// [Regularized incomplete beta function][1] `I(x; a, b)`.
+///
+/// The continued fraction is evaluated by the modified Lentz's method, and it converges
+/// in `O(√max(a, b))` iterations. The function returns [`None`], if it does not converge.
+///
+/// [1]: https://en.wikipedia.org/wiki/Beta_function#Incomplete_beta_function
+fn regularized_incomplete_beta(x: f64, a: f64, b: f64) -> Option<f64> {
+    if x <= 0.0 {
+        return Some(0.0);
+    }
+    if x >= 1.0 {
+        return Some(1.0);
+    }
+    // `Γ(a + b) / Γ(a) / Γ(b) * x^a * (1 - x)^b`, which is related to the binomial probability mass:
+    let log_front = libm::log(a * b / (a + b)) + binomial_log_pmf(a + b, x, a);
+    // The continued fraction converges quickly only below the mean, hence the symmetry:
+    if x < (a + 1.0) / (a + b + 2.0) {
+        Some(libm::exp(log_front) * incomplete_beta_fraction(x, a, b)? / a)
+    } else {
+        Some(1.0 - libm::exp(log_front) * incomplete_beta_fraction(1.0 - x, b, a)? / b)
+    }
+}
+
+/// Continued fraction of the incomplete beta function.
+///
+/// The iterations are capped by `100 + 2√max(a, b)`, which is several times more than it takes
+/// to converge. The function returns [`None`] instead of the unconverged value.
+#[allow(clippy::many_single_char_names)]
+fn incomplete_beta_fraction(x: f64, a: f64, b: f64) -> Option<f64> {
+    const TINY: f64 = 1e-300;
+
+    let max_iterations = 2.0f64.mul_add(a.max(b).sqrt(), 100.0);
+
+    // Replace the zeros to avoid dividing by them:
+    let nonzero = |value: f64| if value.abs() < TINY { TINY } else { value };
+
+    let mut c = 1.0;
+    let mut d = 1.0 / nonzero(1.0 - (a + b) * x / (a + 1.0));
+    let mut fraction = d;
+    let mut m = 1.0;
+    loop {
+        if m > max_iterations {
+            return None;
+        }
+
+        // Even step:
+        let numerator = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 / nonzero(numerator.mul_add(d, 1.0));
+        c = nonzero(1.0 + numerator / c);
+        fraction *= d * c;
+
+        // Odd step:
+        let numerator = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 / nonzero(numerator.mul_add(d, 1.0));
+        c = nonzero(1.0 + numerator / c);
+        let delta = d * c;
+        fraction *= delta;
+
+        if (delta - 1.0).abs() < f64::EPSILON {
+            return Some(fraction);
+        }
+        m += 1.0;
     }
 }
 
 impl<P, D> Density for Binomial<P, D>
 where
-    P: Copy + Into<D> + Zero + PartialOrd + One + Additive,
-    D: Float + Sum,
+    P: Copy + Into<D> + Zero + PartialOrd + One + Additive + CheckedAdd,
+    D: Float,
 {
     type Param = P;
     type Output = D;
@@ -91,47 +324,193 @@ where
     fn density(&self, at: Self::Param) -> Self::Output {
         self.pmf(at) / self.std()
     }
+
+    fn log_density(&self, at: Self::Param) -> Self::Output
+    where
+        Self::Output: Ln,
+    {
+        self.log_pmf(at) - Float::ln(self.std())
+    }
+
+    /// Sum up the probability masses within `[start, end)` in `O(end - start)` operations.
+    ///
+    /// Unlike `cdf(end - 1) - cdf(start - 1)`, the log-sum-exp does not lose precision
+    /// in the tails.
+    ///
+    /// Like the density, the mass is normalized by the standard deviation.
+    fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+    where
+        Self::Param: Copy + ToPrimitive + FromPrimitive,
+        Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        let Range { start, end } = interval;
+        let first = if start < self.offset {
+            self.offset
+        } else {
+            start
+        };
+        let last = self.offset + self.n;
+        if first >= end || first > last {
+            return D::neg_infinity();
+        }
+        let log_sum = log_sum_exp(
+            range_step_from(first, P::one())
+                .take_while(|k| *k < end && *k <= last)
+                .map(|k| self.log_pmf(k)),
+        );
+        log_sum - Float::ln(self.std())
+    }
+}
+
+impl<P, D> Cdf for Binomial<P, D>
+where
+    P: Copy + Into<D> + Zero + PartialOrd + One + Additive,
+    D: Float,
+{
+    type Param = P;
+    type Output = D;
+
+    /// Calculate the CDF in constant time via the regularized incomplete beta function.
+    ///
+    /// Unlike the density, the CDF is not normalized by the standard deviation.
+    fn cdf(&self, at: Self::Param) -> Self::Output {
+        if at < self.offset {
+            return D::zero();
+        }
+        D::from(binomial_cdf(
+            self.n.into().to_f64().unwrap(),
+            self.p.to_f64().unwrap(),
+            (at - self.offset).into().to_f64().unwrap(),
+        ))
+        .unwrap()
+    }
+}
+
+impl<P, D> Quantile for Binomial<P, D>
+where
+    P: Copy + Into<D> + NumCast + Zero + Additive,
+    D: Float,
+{
+    type Param = P;
+    type Probability = D;
+
+    /// Find the smallest point, at which the CDF reaches the probability.
+    ///
+    /// This takes a single CDF evaluation and a constant number of steps on average.
+    fn quantile(&self, probability: Self::Probability) -> Self::Param {
+        self.offset + self.inverse_cdf(probability)
+    }
 }
 
 impl<P, D> Sample for Binomial<P, D>
 where
-    P: Copy + Into<D> + One + Zero + PartialOrd + Additive,
-    D: Float + FromPrimitive + Sum,
+    P: Copy + Into<D> + NumCast + One + Zero + PartialOrd + Additive,
+    D: Float + FromPrimitive,
 {
     type Param = P;
 
+    /// Generate a sample in constant time.
     fn sample(&self, rng: &mut Rng) -> Self::Param {
-        self.inverse_cdf(D::from_f64(rng.f64()).unwrap())
+        let variance = self.std().powi(2).to_f64().unwrap();
+        let n_successes = if variance >= Self::NORMAL_APPROXIMATION_VARIANCE {
+            let normalized = (-2.0 * libm::log(1.0 - rng.f64())).sqrt() * (TAU * rng.f64()).cos();
+            let (n, mean) = (self.n.into(), self.n.into() * self.p);
+            let sample = normalized
+                .mul_add(variance.sqrt(), mean.to_f64().unwrap())
+                .round();
+            P::from(sample.clamp(0.0, n.to_f64().unwrap())).unwrap()
+        } else {
+            self.inverse_cdf(D::from_f64(rng.f64()).unwrap())
+        };
+        self.offset + n_successes
     }
 }
 
 impl<P, D> Kernel for Binomial<P, D>
 where
     Self: Density<Param = P, Output = D> + Sample<Param = P>,
-    P: Copy + Ord + Arithmetic + Into<D> + One,
-    D: Multiplicative,
+    P: Copy
+        + Ord
+        + Arithmetic
+        + Bounded
+        + Into<D>
+        + One
+        + SaturatingAdd
+        + SaturatingSub
+        + SaturatingMul,
+    D: Float,
 {
     type Param = P;
 
+    /// Construct the centred binomial kernel with `p = ½`.
+    ///
+    /// The variance is `n / 4`, so `n = 4σ²`, which is always even.
+    /// Thus, the kernel is symmetric around the location, which is exactly in the middle
+    /// of the support.
+    ///
+    /// Near the bounds of the parameter type, for example, around zero for unsigned types,
+    /// the support gets shifted to fit into the type, and `p` gets adjusted to keep the mean
+    /// at the location. The kernel is then skewed. Too wide a kernel gets narrowed down
+    /// to the whole range of the type.
     fn new(location: P, std: P) -> Self {
-        // Solving these for `p` and `n`:
-        // Bandwidth: σ = √(p(1-p)/n)
-        // Location: l = pn
+        // Restrict bandwidth to avoid the degenerate kernel:
+        let std = std.max(P::one());
+
+        let two = P::one() + P::one();
+        Self::with_half_n(location, std.saturating_mul(&std).saturating_mul(&two))
+    }
 
-        // Getting:
-        // σ² = pn(1-p) = l(1-p)
-        // 1-p = σ²/l
-        // p = 1-(σ²/l)
-        // n = l/p = l/(1-(σ²/l)) = l/((l-σ²)/l) = l²/(l-σ²)
+    /// Construct the kernel with `n = 4σ²` rounded to the nearest even number,
+    /// so that the fractional bandwidths are not truncated.
+    ///
+    /// Just like with [`Kernel::try_new`], the bandwidth must be positive.
+    fn try_new_scaled(
+        location: P,
+        std: f64,
+        bounds: Option<RangeInclusive<P>>,
+    ) -> Result<Self, Error>
+    where
+        P: FromPrimitive,
+    {
+        let _ = bounds;
+        if std.is_nan() || std <= 0.0 {
+            return Err(Error::InvalidBandwidth);
+        }
 
-        // Restrict bandwidth to avoid infinite `n` and/or negative `p`:
-        let sigma_squared = (std * std).min(location - P::one());
+        // Restrict bandwidth to avoid the degenerate kernel:
+        let half_n = libm::round(2.0 * std * std).max(1.0);
 
-        #[allow(clippy::suspicious_operation_groupings)]
-        let n = (location * location / (location - sigma_squared)).max(P::one());
+        // Only too large a number fails to convert, and the kernel gets narrowed down anyway:
+        Ok(Self::with_half_n(
+            location,
+            P::from_f64(half_n).unwrap_or_else(P::max_value),
+        ))
+    }
+}
+
+impl<P, D> Binomial<P, D>
+where
+    P: Copy + Ord + Arithmetic + Bounded + Into<D> + One + SaturatingAdd + SaturatingSub,
+    D: Float,
+{
+    /// Construct the kernel with `n = 2 · half_n` around the location.
+    ///
+    /// The support `offset..=offset + n` always fits into the parameter type,
+    /// so `n` gets capped by the type range.
+    fn with_half_n(location: P, half_n: P) -> Self {
+        let two = P::one() + P::one();
+        let n = half_n
+            .saturating_add(&half_n)
+            .min(P::max_value().saturating_sub(&P::min_value()));
+        let offset = location.saturating_sub(&(n / two)).min(P::max_value() - n);
+
+        // Keep the mean at the location, but away from the support bounds:
+        let one_half = D::one() / (D::one() + D::one());
+        let mean: D = (location - offset).into();
         Self {
             n,
-            p: Into::<D>::into(location) / Into::<D>::into(n),
+            p: mean.max(one_half).min(n.into() - one_half) / n.into(),
+            offset,
         }
     }
 }
@@ -141,54 +520,465 @@ mod tests {
     use approx::assert_abs_diff_eq;
 
     use super::*;
+    use crate::kernel::truncated::Truncated;
 
     #[test]
     fn pmf_ok() {
-        assert_abs_diff_eq!(Binomial { n: 5, p: 0.5 }.pmf(2), 0.3125);
         assert_abs_diff_eq!(
-            Binomial { n: 20, p: 0.5 }.pmf(10),
+            Binomial {
+                n: 5,
+                p: 0.5,
+                offset: 0
+            }
+            .pmf(2),
+            0.3125
+        );
+        assert_abs_diff_eq!(
+            Binomial {
+                n: 20,
+                p: 0.5,
+                offset: 0
+            }
+            .pmf(10),
             0.176_197,
             epsilon = 0.000_001
         );
         assert_abs_diff_eq!(
-            Binomial { n: 20, p: 0.5 }.pmf(5),
+            Binomial {
+                n: 20,
+                p: 0.5,
+                offset: 0
+            }
+            .pmf(5),
             0.014_786,
             epsilon = 0.000_001
         );
-        assert_abs_diff_eq!(Binomial { n: 20_u32, p: 0.5 }.pmf(21_u32), 0.0);
+        assert_abs_diff_eq!(
+            Binomial {
+                n: 20_u32,
+                p: 0.5,
+                offset: 0
+            }
+            .pmf(21_u32),
+            0.0
+        );
     }
 
     #[test]
     fn pmf_corner_cases() {
-        assert_abs_diff_eq!(Binomial { n: 1, p: 0.0 }.pmf(0), 1.0);
-        assert_abs_diff_eq!(Binomial { n: 1, p: 0.0 }.pmf(1), 0.0);
-        assert_abs_diff_eq!(Binomial { n: 1, p: 1.0 }.pmf(0), 0.0);
-        assert_abs_diff_eq!(Binomial { n: 1, p: 1.0 }.pmf(1), 1.0);
+        assert_abs_diff_eq!(
+            Binomial {
+                n: 1,
+                p: 0.0,
+                offset: 0
+            }
+            .pmf(0),
+            1.0
+        );
+        assert_abs_diff_eq!(
+            Binomial {
+                n: 1,
+                p: 0.0,
+                offset: 0
+            }
+            .pmf(1),
+            0.0
+        );
+        assert_abs_diff_eq!(
+            Binomial {
+                n: 1,
+                p: 1.0,
+                offset: 0
+            }
+            .pmf(0),
+            0.0
+        );
+        assert_abs_diff_eq!(
+            Binomial {
+                n: 1,
+                p: 1.0,
+                offset: 0
+            }
+            .pmf(1),
+            1.0
+        );
     }
 
     #[test]
     fn inverse_cdf_ok() {
-        assert_eq!(Binomial { n: 20, p: 0.5 }.inverse_cdf(0.588), 10);
-        assert_eq!(Binomial { n: 20, p: 0.5 }.inverse_cdf(0.020_694), 5);
-        assert_eq!(Binomial { n: 1, p: 0.0 }.inverse_cdf(1.0), 0);
+        assert_eq!(
+            Binomial {
+                n: 20,
+                p: 0.5,
+                offset: 0
+            }
+            .inverse_cdf(0.588),
+            10
+        );
+        assert_eq!(
+            Binomial {
+                n: 20,
+                p: 0.5,
+                offset: 0
+            }
+            .inverse_cdf(0.020_694),
+            5
+        );
+        assert_eq!(
+            Binomial {
+                n: 1,
+                p: 0.0,
+                offset: 0
+            }
+            .inverse_cdf(1.0),
+            0
+        );
+    }
+
+    #[test]
+    fn pmf_large_n_ok() {
+        assert_abs_diff_eq!(
+            Binomial {
+                n: 100_000,
+                p: 0.5,
+                offset: 0
+            }
+            .pmf(50_000),
+            0.002_523_126,
+            epsilon = 0.000_000_001
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn log_density_ok() {
+        let kernel = Binomial::<_, f64>::new(0, 10);
+        assert_abs_diff_eq!(
+            kernel.log_density(5),
+            kernel.density(5).ln(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(kernel.density(200), 0.0);
+        assert!(kernel.log_density(200).is_finite());
+        assert_eq!(kernel.log_density(201), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn sample_ok() {
+        let mut rng = Rng::with_seed(42);
+        for kernel in [
+            Binomial {
+                n: 20,
+                p: 0.1,
+                offset: 0,
+            },
+            Binomial {
+                n: 20,
+                p: 0.9,
+                offset: 0,
+            },
+            Binomial {
+                n: 100_000,
+                p: 0.5,
+                offset: 0,
+            },
+        ] {
+            for _ in 0..100 {
+                assert!((0..=kernel.n).contains(&kernel.sample(&mut rng)));
+            }
+        }
+    }
+
+    #[test]
+    fn sample_f32_ok() {
+        let kernel = Binomial::<i16, f32>::new(0, 9);
+        let mut rng = Rng::with_seed(42);
+        let sum: f64 = (0..1000)
+            .map(|_| -> f64 { kernel.sample(&mut rng).into() })
+            .sum();
+        assert_abs_diff_eq!(sum / 1000.0, 0.0, epsilon = 1.0);
+    }
+
+    #[test]
+    fn inverse_cdf_from_mode_ok() {
+        let kernel = Binomial::<i32, f64> {
+            n: 20,
+            p: 0.5,
+            offset: 0,
+        };
+        for k in 0..=20 {
+            let cdf = kernel.cdf(k);
+            assert_eq!(kernel.inverse_cdf(cdf - 1e-9), k);
+        }
+    }
+
+    #[test]
+    fn inverse_cdf_wide_ok() {
+        let kernel = Binomial::<i32, f64> {
+            n: 1_000_000,
+            p: 0.03,
+            offset: 0,
+        };
+        for probability in [1e-12, 0.001, 0.2, 0.5, 0.9, 0.999_999] {
+            let k = kernel.inverse_cdf(probability);
+            assert!(kernel.cdf(k) >= probability);
+            assert!(kernel.cdf(k - 1) < probability);
+        }
     }
 
     #[test]
     fn std_ok() {
-        assert_abs_diff_eq!(Binomial { n: 20, p: 0.5 }.std(), 2.23607, epsilon = 0.00001);
+        assert_abs_diff_eq!(
+            Binomial {
+                n: 20,
+                p: 0.5,
+                offset: 0
+            }
+            .std(),
+            2.23607,
+            epsilon = 0.00001
+        );
     }
 
     #[test]
     fn new_ok() {
         let kernel = Binomial::<_, f64>::new(5, 2);
-        assert_eq!(kernel.n, 25);
-        assert_abs_diff_eq!(kernel.p, 0.2);
+        assert_eq!(kernel.n, 16);
+        assert_abs_diff_eq!(kernel.p, 0.5);
+        assert_eq!(kernel.offset, -3);
+        assert_abs_diff_eq!(kernel.std(), 2.0);
     }
 
     #[test]
-    fn new_bandwidth_overflow() {
-        let kernel = Binomial::<_, f64>::new(2, 100);
-        assert_eq!(kernel.n, 4);
-        assert_abs_diff_eq!(kernel.p, 0.5);
+    fn new_negative_ok() {
+        let kernel = Binomial::<_, f64>::new(-50, 3);
+        assert_eq!(kernel.offset, -68);
+        assert_abs_diff_eq!(kernel.density(-69), 0.0);
+        assert_abs_diff_eq!(kernel.density(-52), kernel.density(-48));
+        assert!(kernel.density(-50) > kernel.density(-49));
+    }
+
+    #[test]
+    fn new_unsigned_ok() {
+        let kernel = Binomial::<_, f64>::new(2_u32, 3);
+        assert_eq!(kernel.n, 36);
+        assert_eq!(kernel.offset, 0);
+        assert_abs_diff_eq!(kernel.p * 36.0, 2.0);
+    }
+
+    #[test]
+    fn pmf_shifted_ok() {
+        let kernel = Binomial {
+            n: 5,
+            p: 0.5,
+            offset: -10,
+        };
+        assert_abs_diff_eq!(kernel.pmf(-8), 0.3125);
+        assert_abs_diff_eq!(kernel.pmf(-11), 0.0);
+        assert_abs_diff_eq!(kernel.pmf(-4), 0.0);
+    }
+
+    #[test]
+    fn sample_shifted_ok() {
+        let kernel = Binomial::<_, f64>::new(-50, 3);
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..100 {
+            assert!((-68..=-32).contains(&kernel.sample(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn cdf_ok() {
+        let kernel = Binomial::<i32, f64> {
+            n: 4,
+            p: 0.5,
+            offset: -2,
+        };
+        assert_abs_diff_eq!(kernel.cdf(-3), 0.0);
+        assert_abs_diff_eq!(kernel.cdf(-2), 0.0625);
+        assert_abs_diff_eq!(kernel.cdf(0), 0.6875);
+        assert_abs_diff_eq!(kernel.cdf(2), 1.0);
+        assert_abs_diff_eq!(kernel.cdf(10), 1.0);
+    }
+
+    #[test]
+    fn cdf_large_n_ok() {
+        let kernel = Binomial::<i32, f64> {
+            n: 30_000,
+            p: 0.5,
+            offset: 0,
+        };
+        assert_abs_diff_eq!(kernel.cdf(15_000), 0.502_303_275, epsilon = 1e-9);
+        assert_abs_diff_eq!(kernel.cdf(14_500), 4.005e-9, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn cdf_wide_ok() {
+        let kernel = Binomial::<i32, f64> {
+            n: i32::MAX,
+            p: 0.5,
+            offset: -(i32::MAX / 2),
+        };
+
+        // The support is odd, so the CDF is exactly ½ at the middle, and symmetric around it:
+        let middle = 0;
+        assert_abs_diff_eq!(kernel.cdf(middle), 0.5, epsilon = 1e-10);
+        assert_abs_diff_eq!(
+            kernel.cdf(middle - 23_170) + kernel.cdf(middle + 23_170),
+            1.0,
+            epsilon = 1e-10
+        );
+
+        // The references are summed up over the probability masses with the arbitrary precision:
+        assert_abs_diff_eq!(
+            kernel.cdf(middle - 23_170),
+            0.158_660_214_442_604_9,
+            epsilon = 1e-10
+        );
+        assert_abs_diff_eq!(
+            kernel.cdf(middle - 100_000),
+            7.949_953_361_842_626e-6,
+            epsilon = 1e-14
+        );
+    }
+
+    #[test]
+    fn truncated_wide_bounds_ok() {
+        let kernel =
+            Truncated::<Binomial<i32, f64>>::new_bounded(0, 30_000, -1_000_000..=1_000_000);
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..100 {
+            assert!((-1_000_000..=1_000_000).contains(&kernel.sample(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn truncated_ok() {
+        // The mass at the lower bound stays within the bounds:
+        let kernel = Truncated::<Binomial<i32, f64>>::new_bounded(0, 2, 0..=3);
+
+        // Like the inner density, the truncated one is normalized by the standard deviation:
+        assert_abs_diff_eq!(
+            (0..=3).map(|at| kernel.density(at)).sum::<f64>() * 2.0,
+            1.0,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(kernel.cdf(0), 12_870.0 / 36_686.0, epsilon = 1e-12);
+
+        let mut rng = Rng::with_seed(42);
+        let mut counts = [0; 4];
+        for _ in 0..1_000 {
+            counts[usize::try_from(kernel.sample(&mut rng)).unwrap()] += 1;
+        }
+        assert!(counts.iter().all(|count| *count > 50), "{counts:?}");
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn log_mass_ok() {
+        let kernel = Binomial::<i32, f64> {
+            n: 4,
+            p: 0.5,
+            offset: -2,
+        };
+        assert_abs_diff_eq!(kernel.log_mass(-2..0), 0.3125_f64.ln(), epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.log_mass(-1..1), 0.625_f64.ln(), epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.log_mass(-10..10), 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.log_mass(2..5), 0.0625_f64.ln(), epsilon = 1e-12);
+        assert_eq!(kernel.log_mass(3..5), f64::NEG_INFINITY);
+
+        // The support ends at the type maximum, and the iteration must not overflow:
+        let kernel = Binomial::<u8, f64> {
+            n: 4,
+            p: 0.5,
+            offset: 251,
+        };
+        assert_abs_diff_eq!(
+            kernel.log_mass(0..u8::MAX),
+            0.9375_f64.ln(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            kernel.log_mass(254..u8::MAX),
+            0.25_f64.ln(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn quantile_ok() {
+        let kernel = Binomial::<i32, f64> {
+            n: 4,
+            p: 0.5,
+            offset: -2,
+        };
+        assert_eq!(kernel.quantile(0.0), -2);
+        assert_eq!(kernel.quantile(0.0625), -2);
+        assert_eq!(kernel.quantile(0.5), 0);
+        assert_eq!(kernel.quantile(0.6875), 0);
+        assert_eq!(kernel.quantile(0.7), 1);
+        assert_eq!(kernel.quantile(1.0), 2);
+    }
+
+    #[test]
+    fn new_near_max_ok() {
+        let kernel = Binomial::<u8, f64>::new(250, 5);
+        assert_eq!((kernel.n, kernel.offset), (100, 155));
+        assert_abs_diff_eq!(kernel.p * 100.0, 95.0);
+
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..100 {
+            assert!(kernel.sample(&mut rng) >= 155);
+        }
+    }
+
+    #[test]
+    fn new_wide_ok() {
+        // The support gets capped by the type range, instead of overflowing:
+        let kernel = Binomial::<i32, f64>::new(0, 50_000);
+        assert_eq!(kernel.n, i32::MAX);
+        assert_eq!(kernel.offset, -(i32::MAX / 2));
+        assert_abs_diff_eq!(kernel.p, 0.5, epsilon = 1e-9);
+        assert!(kernel.sample(&mut Rng::with_seed(42)) >= kernel.offset);
+
+        let kernel = Binomial::<u8, f64>::try_new_scaled(10, 1e6, None).unwrap();
+        assert_eq!((kernel.n, kernel.offset), (u8::MAX, 0));
+    }
+
+    #[test]
+    fn try_new_ok() {
+        assert!(Binomial::<i32, f64>::try_new(0, 30_000).is_ok());
+        assert_eq!(
+            Binomial::<i32, f64>::try_new(0, 0),
+            Err(Error::InvalidBandwidth)
+        );
+    }
+
+    #[test]
+    fn try_new_scaled_ok() {
+        let kernel = Binomial::<i32, f64>::try_new_scaled(10, 1.5, None).unwrap();
+        assert_eq!((kernel.n, kernel.offset), (10, 5));
+
+        // Unlike the integer bandwidth, the fractional one is not restricted to `1`:
+        let kernel = Binomial::<i32, f64>::try_new_scaled(10, 0.5, None).unwrap();
+        assert_eq!((kernel.n, kernel.offset), (2, 9));
+
+        assert_eq!(
+            Binomial::<i32, f64>::try_new_scaled(10, f64::NAN, None),
+            Err(Error::InvalidBandwidth)
+        );
+    }
+
+    #[test]
+    fn try_new_scaled_zero_err() {
+        // Just like with `try_new(0, 0)`, the non-positive bandwidth is an error:
+        assert_eq!(
+            Binomial::<i32, f64>::try_new_scaled(10, 0.0, None),
+            Err(Error::InvalidBandwidth)
+        );
+        assert_eq!(
+            Binomial::<i32, f64>::try_new_scaled(10, -1.0, None),
+            Err(Error::InvalidBandwidth)
+        );
     }
 }
@@ -0,0 +1,209 @@
+use core::{
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+};
+
+use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{FromPrimitive, One, PrimInt, ToPrimitive, Zero};
+
+use crate::{
+    kernel::{discrete::round_saturating, Kernel},
+    traits::ops::{Arithmetic, Exp, Ln},
+    Density,
+    Error,
+    Sample,
+};
+
+/// Kernel for unordered categories, which are encoded as indices.
+///
+/// It is the mixture of the point mass at the location and the uniform distribution
+/// over all the categories within the bounds, similar to the [Aitchison–Aitken kernel][1].
+/// Unlike the other discrete kernels, it does not favour the indices next to the location,
+/// so that it does not impose any order on the categories.
+///
+/// The bandwidth `σ` sets the uniform weight `σ / (1 + σ)`: a narrow kernel sticks
+/// to the location, and a wide one approaches the uniform distribution.
+/// Without the bounds, the categories are unknown, and the kernel is the point mass.
+///
+/// [1]: https://doi.org/10.1093/biomet/63.3.413
+#[derive(Copy, Clone, Debug)]
+pub struct Categorical<P, D> {
+    location: P,
+
+    /// Inclusive bounds of the category indices.
+    min: P,
+    max: P,
+
+    /// Weight of the uniform component.
+    weight: f64,
+
+    _density: PhantomData<D>,
+}
+
+impl<P: PrimInt, D> Categorical<P, D> {
+    /// Construct the kernel with the real-valued standard deviation.
+    fn with_std(location: P, std: f64, bounds: Option<RangeInclusive<P>>) -> Self {
+        let (min, max) = bounds.map_or((location, location), RangeInclusive::into_inner);
+        Self {
+            location,
+            min,
+            max,
+            weight: std / (1.0 + std),
+            _density: PhantomData,
+        }
+    }
+
+    fn n_categories(&self) -> f64 {
+        self.max.to_f64().unwrap() - self.min.to_f64().unwrap() + 1.0
+    }
+
+    /// Probability mass function.
+    fn pmf(&self, at: P) -> f64 {
+        let uniform = if (self.min..=self.max).contains(&at) {
+            self.weight / self.n_categories()
+        } else {
+            0.0
+        };
+        if at == self.location {
+            1.0 - self.weight + uniform
+        } else {
+            uniform
+        }
+    }
+}
+
+impl<P, D> Density for Categorical<P, D>
+where
+    P: PrimInt,
+    D: FromPrimitive,
+{
+    type Param = P;
+    type Output = D;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        D::from_f64(self.pmf(at)).unwrap()
+    }
+
+    fn log_density(&self, at: Self::Param) -> Self::Output
+    where
+        Self::Output: Ln,
+    {
+        D::from_f64(libm::log(self.pmf(at))).unwrap()
+    }
+
+    /// Calculate the probability mass of the indices within `[start, end)`.
+    fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+    where
+        Self::Param: Copy + ToPrimitive + FromPrimitive,
+        Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        let Range { start, end } = interval;
+        let mass = if start < end {
+            let first = start.max(self.min).to_f64().unwrap();
+            let last = (end - P::one()).min(self.max).to_f64().unwrap();
+            let uniform = self.weight * (last - first + 1.0).max(0.0) / self.n_categories();
+            if (start..end).contains(&self.location) {
+                1.0 - self.weight + uniform
+            } else {
+                uniform
+            }
+        } else {
+            0.0
+        };
+        D::from_f64(libm::log(mass)).unwrap()
+    }
+}
+
+impl<P: PrimInt, D> Sample for Categorical<P, D> {
+    type Param = P;
+
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        if rng.f64() < self.weight {
+            let min = self.min.to_f64().unwrap();
+            round_saturating(min + (rng.f64() * self.n_categories()).floor())
+        } else {
+            self.location
+        }
+    }
+}
+
+impl<P, D> Kernel for Categorical<P, D>
+where
+    Self: Density<Param = P, Output = D> + Sample<Param = P>,
+    P: PrimInt,
+{
+    type Param = P;
+
+    fn new(location: P, std: P) -> Self {
+        assert!(std > P::zero());
+        Self::with_std(location, std.to_f64().unwrap(), None)
+    }
+
+    fn new_bounded(location: P, std: P, bounds: RangeInclusive<P>) -> Self {
+        assert!(std > P::zero());
+        Self::with_std(location, std.to_f64().unwrap(), Some(bounds))
+    }
+
+    /// Construct the kernel with the real-valued bandwidth as is.
+    fn try_new_scaled(
+        location: P,
+        std: f64,
+        bounds: Option<RangeInclusive<P>>,
+    ) -> Result<Self, Error> {
+        if std.is_finite() && std > 0.0 {
+            Ok(Self::with_std(location, std, bounds))
+        } else {
+            Err(Error::InvalidBandwidth)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn density_ok() {
+        let kernel = Categorical::<i32, f64>::new_bounded(1, 1, 0..=3);
+        assert_abs_diff_eq!(kernel.density(-1), 0.0);
+        assert_abs_diff_eq!(kernel.density(0), 0.125);
+        assert_abs_diff_eq!(kernel.density(1), 0.625);
+        assert_abs_diff_eq!(kernel.density(3), 0.125);
+        assert_abs_diff_eq!(kernel.density(4), 0.0);
+    }
+
+    #[test]
+    fn unbounded_ok() {
+        let kernel = Categorical::<i32, f64>::new(1, 1);
+        assert_abs_diff_eq!(kernel.density(0), 0.0);
+        assert_abs_diff_eq!(kernel.density(1), 1.0);
+        assert_eq!(kernel.sample(&mut Rng::with_seed(42)), 1);
+    }
+
+    #[test]
+    fn log_mass_ok() {
+        let kernel = Categorical::<i32, f64>::new_bounded(1, 1, 0..=3);
+        assert_abs_diff_eq!(kernel.log_mass(-10..10), 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.log_mass(0..2), 0.75_f64.ln(), epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.log_mass(2..10), 0.25_f64.ln(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn sample_ok() {
+        // The neighbours of the location are sampled no more often than the far categories:
+        let kernel = Categorical::<u8, f64>::try_new_scaled(1, 3.0, Some(0..=3)).unwrap();
+        let mut rng = Rng::with_seed(42);
+        let mut counts = [0; 4];
+        for _ in 0..10_000 {
+            counts[usize::from(kernel.sample(&mut rng))] += 1;
+        }
+        assert!((4_200..=4_550).contains(&counts[1]), "{counts:?}");
+        for index in [0, 2, 3] {
+            assert!((1_700..=2_050).contains(&counts[index]), "{counts:?}");
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use core::{
+    f64::consts::{SQRT_2, TAU},
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+};
+
+use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{FromPrimitive, One, PrimInt, ToPrimitive, Zero};
+
+use crate::{
+    kernel::{discrete::round_saturating, Kernel},
+    traits::ops::{Arithmetic, Exp, Ln},
+    Density,
+    Error,
+    Sample,
+};
+
+/// [Discretized Gaussian][1] kernel.
+///
+/// The probability mass of each integer is the Gaussian density integrated over its unit cell,
+/// so that sampling is just rounding the Gaussian samples. The samples saturate at the bounds
+/// of the parameter type, so the bounds get the whole mass of the tails beyond them.
+///
+/// [1]: https://en.wikipedia.org/wiki/Discrete_Gaussian_kernel
+#[derive(Copy, Clone, Debug)]
+pub struct DiscreteGaussian<P, D> {
+    location: P,
+    std: f64,
+    _density: PhantomData<D>,
+}
+
+impl<P: PrimInt, D> DiscreteGaussian<P, D> {
+    /// Construct the kernel with the real-valued standard deviation.
+    const fn with_std(location: P, std: f64) -> Self {
+        Self {
+            location,
+            std,
+            _density: PhantomData,
+        }
+    }
+
+    /// Standard normal cumulative distribution function at the specified offset from the location.
+    fn cdf(&self, offset: f64) -> f64 {
+        0.5 * libm::erfc(-offset / self.std / SQRT_2)
+    }
+
+    /// Probability mass of the unit cell around the point.
+    fn mass(&self, at: P) -> f64 {
+        let offset = at.to_f64().unwrap() - self.location.to_f64().unwrap();
+        if at == P::max_value() {
+            self.cdf(0.5 - offset)
+        } else if at == P::min_value() {
+            self.cdf(offset + 0.5)
+        } else {
+            // Integrate over the farther tail to avoid the precision loss:
+            let offset = -offset.abs();
+            self.cdf(offset + 0.5) - self.cdf(offset - 0.5)
+        }
+    }
+}
+
+impl<P, D> Density for DiscreteGaussian<P, D>
+where
+    P: PrimInt,
+    D: FromPrimitive,
+{
+    type Param = P;
+    type Output = D;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        D::from_f64(self.mass(at)).unwrap()
+    }
+
+    /// Calculate the log-mass directly, so that it does not underflow in the far tails.
+    ///
+    /// Where the mass underflows, the tail probabilities `Q(z)` beyond the cell edges
+    /// are approximated by the [Mills ratio][1] `ln Q(z) ≈ -z² / 2 - ln(z√(2π))`.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Mills_ratio
+    fn log_density(&self, at: Self::Param) -> Self::Output
+    where
+        Self::Output: Ln,
+    {
+        let mass = self.mass(at);
+        let log_mass = if mass >= f64::MIN_POSITIVE {
+            libm::log(mass)
+        } else {
+            let log_tail = |z: f64| (-0.5 * z).mul_add(z, -libm::log(z * TAU.sqrt()));
+            let offset = (at.to_f64().unwrap() - self.location.to_f64().unwrap()).abs();
+            let near_tail = log_tail((offset - 0.5) / self.std);
+            if at == P::max_value() || at == P::min_value() {
+                near_tail
+            } else {
+                let far_tail = log_tail((offset + 0.5) / self.std);
+                near_tail + libm::log1p(-libm::exp(far_tail - near_tail))
+            }
+        };
+        D::from_f64(log_mass).unwrap()
+    }
+
+    /// Integrate the Gaussian density over the cells within `[start, end)`.
+    ///
+    /// Where the mass underflows, the cell nearest to the location carries almost all of it,
+    /// so its log-density is used instead.
+    fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+    where
+        Self::Param: Copy + ToPrimitive + FromPrimitive,
+        Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        let Range { start, end } = interval;
+        if start >= end {
+            return D::from_f64(f64::NEG_INFINITY).unwrap();
+        }
+        let last = end - P::one();
+        let location = self.location.to_f64().unwrap();
+        let lower = start.to_f64().unwrap() - location - 0.5;
+        let upper = last.to_f64().unwrap() - location + 0.5;
+
+        // The bounds of the parameter type get the whole mass of the tails beyond them:
+        let lower_tail = |offset| {
+            if start == P::min_value() {
+                0.0
+            } else {
+                self.cdf(offset)
+            }
+        };
+        let upper_tail = |offset: f64| {
+            if last == P::max_value() {
+                0.0
+            } else {
+                self.cdf(-offset)
+            }
+        };
+
+        // Integrate over the farther tail to avoid the precision loss:
+        let mass = if lower >= 0.0 {
+            self.cdf(-lower) - upper_tail(upper)
+        } else if upper <= 0.0 {
+            self.cdf(upper) - lower_tail(lower)
+        } else {
+            1.0 - lower_tail(lower) - upper_tail(upper)
+        };
+        if mass >= f64::MIN_POSITIVE {
+            D::from_f64(libm::log(mass)).unwrap()
+        } else {
+            self.log_density(self.location.max(start).min(last))
+        }
+    }
+}
+
+impl<P: PrimInt, D> Sample for DiscreteGaussian<P, D> {
+    type Param = P;
+
+    /// Generate a sample by rounding the [Box–Muller][1] sample.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Box–Muller_transform
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        let normalized = (-2.0 * libm::log(1.0 - rng.f64())).sqrt() * (TAU * rng.f64()).cos();
+        round_saturating(normalized.mul_add(self.std, self.location.to_f64().unwrap()))
+    }
+}
+
+impl<P, D> Kernel for DiscreteGaussian<P, D>
+where
+    Self: Density<Param = P, Output = D> + Sample<Param = P>,
+    P: PrimInt,
+{
+    type Param = P;
+
+    fn new(location: P, std: P) -> Self {
+        assert!(std > P::zero());
+        Self::with_std(location, std.to_f64().unwrap())
+    }
+
+    /// Construct the kernel with the real-valued bandwidth as is, ignoring the bounds.
+    fn try_new_scaled(
+        location: P,
+        std: f64,
+        bounds: Option<RangeInclusive<P>>,
+    ) -> Result<Self, Error> {
+        let _ = bounds;
+        if std.is_finite() && std > 0.0 {
+            Ok(Self::with_std(location, std))
+        } else {
+            Err(Error::InvalidBandwidth)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn density_ok() {
+        let kernel = DiscreteGaussian::<_, f64>::new(-10, 1);
+        assert_abs_diff_eq!(kernel.density(-10), 0.382_924_922_548_026, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            kernel.density(-11),
+            0.241_730_337_457_129_8,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(kernel.density(-9), kernel.density(-11));
+        assert_abs_diff_eq!((-30..=10).map(|at| kernel.density(at)).sum::<f64>(), 1.0);
+    }
+
+    #[test]
+    fn log_density_ok() {
+        let kernel = DiscreteGaussian::<_, f64>::new(-10, 1);
+        assert_abs_diff_eq!(
+            kernel.log_density(-11),
+            0.241_730_337_457_129_8_f64.ln(),
+            epsilon = 1e-12
+        );
+
+        // The mass underflows here, but the log-density does not:
+        assert_abs_diff_eq!(kernel.density(90), 0.0);
+        assert_abs_diff_eq!(kernel.log_density(90), -4_955.644_197, epsilon = 1e-3);
+
+        // Around the underflow, where the approximation takes over:
+        let kernel = DiscreteGaussian::<_, f64>::new(0, 1);
+        assert_abs_diff_eq!(kernel.log_density(38), -707.668_989, epsilon = 1e-3);
+        assert_abs_diff_eq!(kernel.log_density(-39), -745.695_270, epsilon = 1e-3);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn log_mass_ok() {
+        let kernel = DiscreteGaussian::<_, f64>::new(64, 1);
+        assert_abs_diff_eq!(
+            kernel.log_mass(48..80),
+            libm::log((48..80).map(|at| kernel.density(at)).sum::<f64>()),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            (-10..10)
+                .map(|i| kernel.log_mass(i * 32..(i + 1) * 32).exp())
+                .sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+        assert_eq!(kernel.log_mass(64..64), f64::NEG_INFINITY);
+
+        // The mass underflows, but the log-mass does not:
+        assert_abs_diff_eq!(
+            kernel.log_mass(164..200),
+            kernel.log_density(164),
+            epsilon = 1e-3
+        );
+
+        // The bounds get the tail mass:
+        let kernel = DiscreteGaussian::<u8, f64>::new(1, 5);
+        assert_abs_diff_eq!(
+            kernel.log_mass(0..3).exp(),
+            (0..3).map(|at| kernel.density(at)).sum::<f64>(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn sample_ok() {
+        let kernel = DiscreteGaussian::<u8, f64>::new(1, 5);
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..100 {
+            let _ = kernel.sample(&mut rng);
+        }
+    }
+
+    #[test]
+    fn wide_types_ok() {
+        let kernel = DiscreteGaussian::<i128, f64>::new(i128::MAX, 1);
+        assert_abs_diff_eq!(
+            kernel.density(i128::MAX),
+            0.691_462_461_274_013,
+            epsilon = 1e-12
+        );
+        assert_eq!(kernel.sample(&mut Rng::with_seed(42)), i128::MAX);
+    }
+
+    #[test]
+    fn near_bounds_ok() {
+        // The samples saturate at the type bounds, so the bounds get the tails:
+        let kernel = DiscreteGaussian::<u8, f64>::new(1, 2);
+        assert_abs_diff_eq!(kernel.density(0), 0.401_293_674_317_076, epsilon = 1e-12);
+        assert_abs_diff_eq!((0..=255).map(|at| kernel.density(at)).sum::<f64>(), 1.0);
+
+        let mut rng = Rng::with_seed(42);
+        let n_minima = (0..10_000).filter(|_| kernel.sample(&mut rng) == 0).count();
+        assert!((3_800..=4_200).contains(&n_minima));
+    }
+
+    #[test]
+    fn try_new_scaled_ok() {
+        let kernel = DiscreteGaussian::<i32, f64>::try_new_scaled(0, 0.5, None).unwrap();
+        assert_abs_diff_eq!(kernel.std, 0.5);
+        assert!(DiscreteGaussian::<i32, f64>::try_new_scaled(0, 0.0, None).is_err());
+        assert!(DiscreteGaussian::<i32, f64>::try_new_scaled(0, f64::NAN, None).is_err());
+    }
+}
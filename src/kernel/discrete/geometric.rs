@@ -0,0 +1,325 @@
+use core::{
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+};
+
+use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{FromPrimitive, One, PrimInt, ToPrimitive, Zero};
+
+use crate::{
+    kernel::{discrete::round_saturating, Kernel},
+    traits::ops::{Arithmetic, Exp, Ln},
+    Density,
+    Error,
+    Sample,
+};
+
+/// Symmetric [geometric][1] kernel, also known as «discrete Laplace».
+///
+/// The probability mass decays geometrically with the distance from the location:
+/// `(1 - q) / (1 + q) * q^|k - location|`. Its tails are heavier than the [`super::DiscreteGaussian`]
+/// ones, which favors exploration. The samples saturate at the bounds of the parameter type,
+/// so the bounds get the whole mass of the tails beyond them.
+///
+/// [1]: https://en.wikipedia.org/wiki/Geometric_distribution
+#[derive(Copy, Clone, Debug)]
+pub struct Geometric<P, D> {
+    location: P,
+
+    /// Ratio of the probabilities of the adjacent values, `0 ≤ q < 1`.
+    ratio: f64,
+
+    _density: PhantomData<D>,
+}
+
+impl<P, D> Geometric<P, D> {
+    /// Construct the kernel with the real-valued standard deviation.
+    fn with_std(location: P, std: f64) -> Self {
+        // Solving `σ² = 2q / (1 - q)²` for `q`:
+        let variance = std.powi(2);
+        let ratio = (variance + 1.0 - 2.0_f64.mul_add(variance, 1.0).sqrt()) / variance;
+
+        Self {
+            location,
+            ratio,
+            _density: PhantomData,
+        }
+    }
+
+    /// Natural logarithm of `q^distance`.
+    fn log_decay(&self, distance: f64) -> f64 {
+        // Avoid `0 · ln(0)` for the degenerate kernel:
+        if distance == 0.0 {
+            0.0
+        } else {
+            distance * libm::log(self.ratio)
+        }
+    }
+
+    /// Natural logarithm of the tail probability `q^distance / (1 + q)` of the samples,
+    /// which are at least the specified distance away from the location on one side.
+    fn log_tail(&self, distance: f64) -> f64 {
+        self.log_decay(distance) - libm::log1p(self.ratio)
+    }
+}
+
+impl<P: PrimInt, D> Geometric<P, D> {
+    /// Natural logarithm of the probability mass function.
+    fn log_pmf(&self, at: P) -> f64 {
+        let distance = (at.to_f64().unwrap() - self.location.to_f64().unwrap()).abs();
+        if at == P::max_value() || at == P::min_value() {
+            self.log_tail(distance)
+        } else {
+            libm::log((1.0 - self.ratio) / (1.0 + self.ratio)) + self.log_decay(distance)
+        }
+    }
+}
+
+impl<P, D> Density for Geometric<P, D>
+where
+    P: PrimInt,
+    D: FromPrimitive,
+{
+    type Param = P;
+    type Output = D;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        D::from_f64(libm::exp(self.log_pmf(at))).unwrap()
+    }
+
+    fn log_density(&self, at: Self::Param) -> Self::Output
+    where
+        Self::Output: Ln,
+    {
+        D::from_f64(self.log_pmf(at)).unwrap()
+    }
+
+    /// Sum up the geometric series of the masses within `[start, end)` in constant time.
+    fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+    where
+        Self::Param: Copy + ToPrimitive + FromPrimitive,
+        Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        let Range { start, end } = interval;
+        if start >= end {
+            return D::from_f64(f64::NEG_INFINITY).unwrap();
+        }
+        let last = end - P::one();
+        let location = self.location.to_f64().unwrap();
+        let (first_distance, last_distance) = (
+            start.to_f64().unwrap() - location,
+            last.to_f64().unwrap() - location,
+        );
+
+        // One-sided interval is the difference of the tails at its nearest and farthest values,
+        // unless the latter is the bound of the parameter type, which gets the whole tail:
+        let log_one_sided = |near: f64, far: f64, is_bound: bool| {
+            let log_tail = self.log_tail(near);
+            if is_bound {
+                log_tail
+            } else {
+                log_tail + libm::log1p(-libm::exp(self.log_decay(far - near + 1.0)))
+            }
+        };
+        let log_mass = if start > self.location {
+            log_one_sided(first_distance, last_distance, last == P::max_value())
+        } else if last < self.location {
+            log_one_sided(-last_distance, -first_distance, start == P::min_value())
+        } else {
+            let lower_tail = if start == P::min_value() {
+                0.0
+            } else {
+                libm::exp(self.log_tail(1.0 - first_distance))
+            };
+            let upper_tail = if last == P::max_value() {
+                0.0
+            } else {
+                libm::exp(self.log_tail(last_distance + 1.0))
+            };
+            libm::log1p(-lower_tail - upper_tail)
+        };
+        D::from_f64(log_mass).unwrap()
+    }
+}
+
+impl<P: PrimInt, D> Sample for Geometric<P, D> {
+    type Param = P;
+
+    /// Generate a sample as a difference of two iid [geometric][1] samples.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Geometric_distribution#Random_variate_generation
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        let mut sample_geometric = || {
+            if self.ratio == 0.0 {
+                0.0
+            } else {
+                (1.0 - rng.f64()).log(self.ratio).floor()
+            }
+        };
+        let offset = sample_geometric() - sample_geometric();
+        round_saturating(self.location.to_f64().unwrap() + offset)
+    }
+}
+
+impl<P, D> Kernel for Geometric<P, D>
+where
+    Self: Density<Param = P, Output = D> + Sample<Param = P>,
+    P: PrimInt,
+{
+    type Param = P;
+
+    fn new(location: P, std: P) -> Self {
+        assert!(std > P::zero());
+        Self::with_std(location, std.to_f64().unwrap())
+    }
+
+    /// Construct the kernel with the real-valued bandwidth as is, ignoring the bounds.
+    fn try_new_scaled(
+        location: P,
+        std: f64,
+        bounds: Option<RangeInclusive<P>>,
+    ) -> Result<Self, Error> {
+        let _ = bounds;
+        if std.is_finite() && std > 0.0 {
+            Ok(Self::with_std(location, std))
+        } else {
+            Err(Error::InvalidBandwidth)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn new_ok() {
+        let kernel = Geometric::<_, f64>::new(0, 2);
+        assert_abs_diff_eq!(kernel.ratio, 0.5);
+    }
+
+    #[test]
+    fn try_new_scaled_ok() {
+        let kernel = Geometric::<i32, f64>::try_new_scaled(0, 0.5, None).unwrap();
+        assert!(kernel.ratio > 0.0 && kernel.ratio < 0.5);
+        assert!(Geometric::<i32, f64>::try_new_scaled(0, -1.0, None).is_err());
+    }
+
+    #[test]
+    fn density_ok() {
+        let kernel = Geometric::<_, f64>::new(-5, 2);
+        assert_abs_diff_eq!(kernel.density(-5), 1.0 / 3.0);
+        assert_abs_diff_eq!(kernel.density(-7), 1.0 / 12.0);
+        assert_abs_diff_eq!(kernel.density(-3), 1.0 / 12.0);
+        assert_abs_diff_eq!(
+            (-100..=100).map(|at| kernel.density(at)).sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn log_density_ok() {
+        let kernel = Geometric::<_, f64>::new(-5, 2);
+        assert_abs_diff_eq!(
+            kernel.log_density(-7),
+            libm::log(kernel.density(-7)),
+            epsilon = 1e-12
+        );
+
+        // The density underflows, but the log-density does not:
+        assert_abs_diff_eq!(kernel.density(1995), 0.0);
+        assert_abs_diff_eq!(
+            kernel.log_density(1995),
+            libm::log(1.0 / 3.0) - 2000.0 * core::f64::consts::LN_2,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn log_mass_ok() {
+        let kernel = Geometric::<_, f64>::new(64, 1);
+        for interval in [48..80, 60..64, 65..70, 64..65] {
+            assert_abs_diff_eq!(
+                kernel.log_mass(interval.clone()),
+                libm::log(interval.map(|at| kernel.density(at)).sum::<f64>()),
+                epsilon = 1e-12
+            );
+        }
+        assert_abs_diff_eq!(
+            (-10..10)
+                .map(|i| kernel.log_mass(i * 32..(i + 1) * 32).exp())
+                .sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+        assert_eq!(kernel.log_mass(64..64), f64::NEG_INFINITY);
+
+        // The mass underflows, but the log-mass does not:
+        assert_abs_diff_eq!(
+            kernel.log_mass(2064..3000),
+            kernel.log_density(2064) - libm::log1p(-kernel.ratio),
+            epsilon = 1e-9
+        );
+
+        // The bounds get the tail mass:
+        let kernel = Geometric::<u8, f64>::new(1, 2);
+        for interval in [0..3, 2..10, 200..u8::MAX] {
+            assert_abs_diff_eq!(
+                kernel.log_mass(interval.clone()).exp(),
+                interval.map(|at| kernel.density(at)).sum::<f64>(),
+                epsilon = 1e-12
+            );
+        }
+        assert_abs_diff_eq!(
+            kernel.log_mass(0..128).exp()
+                + kernel.log_mass(128..u8::MAX).exp()
+                + kernel.density(u8::MAX),
+            1.0,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn sample_ok() {
+        let kernel = Geometric::<i64, f64>::new(-5, 2);
+        let mut rng = Rng::with_seed(42);
+        let samples: Vec<i64> = (0..10_000).map(|_| kernel.sample(&mut rng)).collect();
+        let mean = samples.iter().sum::<i64>() as f64 / 10_000.0;
+        let variance = samples
+            .iter()
+            .map(|sample| (*sample as f64 - mean).powi(2))
+            .sum::<f64>()
+            / 10_000.0;
+        assert_abs_diff_eq!(mean, -5.0, epsilon = 0.1);
+        assert_abs_diff_eq!(variance, 4.0, epsilon = 0.2);
+    }
+
+    #[test]
+    fn type_bounds_ok() {
+        let kernel = Geometric::<u8, f64>::new(1, 2);
+        assert_abs_diff_eq!(kernel.density(0), 1.0 / 3.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.density(1), 1.0 / 3.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            (0..=u8::MAX).map(|at| kernel.density(at)).sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+
+        // The samples saturate at zero as often, as the density says:
+        let mut rng = Rng::with_seed(42);
+        let n_zeros = (0..10_000).filter(|_| kernel.sample(&mut rng) == 0).count();
+        assert!((3_200..=3_450).contains(&n_zeros), "{n_zeros}");
+
+        let kernel = Geometric::<u8, f64>::new(u8::MAX, 2);
+        assert_abs_diff_eq!(kernel.density(u8::MAX), 2.0 / 3.0, epsilon = 1e-12);
+    }
+}
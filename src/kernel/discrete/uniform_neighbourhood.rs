@@ -0,0 +1,226 @@
+use core::{
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+};
+
+use fastrand::Rng;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{FromPrimitive, One, PrimInt, ToPrimitive, Zero};
+
+use crate::{
+    kernel::{discrete::round_saturating, Kernel},
+    traits::ops::{Arithmetic, Exp, Ln},
+    Density,
+    Error,
+    Sample,
+};
+
+/// Uniform kernel over the small neighbourhood `location - radius..=location + radius`.
+///
+/// Unlike [`crate::kernel::universal::Uniform`], it is built around the location,
+/// which makes it a simple local search kernel. Near the bounds of the parameter type,
+/// the neighbourhood gets clipped by them.
+#[derive(Copy, Clone, Debug)]
+pub struct UniformNeighbourhood<P, D> {
+    location: P,
+    radius: P,
+    _density: PhantomData<D>,
+}
+
+impl<P: PrimInt, D> UniformNeighbourhood<P, D> {
+    /// Construct the kernel with the real-valued standard deviation.
+    fn with_std(location: P, std: f64) -> Self {
+        // Solving `σ² = r(r + 1) / 3` for the radius, but at least one neighbour on each side:
+        let radius = ((12.0_f64.mul_add(std.powi(2), 1.0).sqrt() - 1.0) / 2.0).max(1.0);
+
+        Self {
+            location,
+            radius: round_saturating(radius),
+            _density: PhantomData,
+        }
+    }
+
+    /// Support of the kernel, clipped by the bounds of the parameter type.
+    fn support(&self) -> (f64, f64) {
+        let location = self.location.to_f64().unwrap();
+        let radius = self.radius.to_f64().unwrap();
+        (
+            (location - radius).max(P::min_value().to_f64().unwrap()),
+            (location + radius).min(P::max_value().to_f64().unwrap()),
+        )
+    }
+
+    /// Natural logarithm of the probability mass function.
+    fn log_pmf(&self, at: P) -> f64 {
+        let at = at.to_f64().unwrap();
+        let (start, end) = self.support();
+        if (start..=end).contains(&at) {
+            -libm::log(end - start + 1.0)
+        } else {
+            f64::NEG_INFINITY
+        }
+    }
+}
+
+impl<P, D> Density for UniformNeighbourhood<P, D>
+where
+    P: PrimInt,
+    D: FromPrimitive,
+{
+    type Param = P;
+    type Output = D;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        D::from_f64(libm::exp(self.log_pmf(at))).unwrap()
+    }
+
+    fn log_density(&self, at: Self::Param) -> Self::Output
+    where
+        Self::Output: Ln,
+    {
+        D::from_f64(self.log_pmf(at)).unwrap()
+    }
+
+    /// Calculate the share of the neighbourhood within `[start, end)`.
+    fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+    where
+        Self::Param: Copy + ToPrimitive + FromPrimitive,
+        Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        let Range { start, end } = interval;
+        if start >= end {
+            return D::from_f64(f64::NEG_INFINITY).unwrap();
+        }
+        let (support_start, support_end) = self.support();
+        let first = start.to_f64().unwrap().max(support_start);
+        let last = (end - P::one()).to_f64().unwrap().min(support_end);
+        let n_values = (last - first + 1.0).max(0.0);
+        D::from_f64(libm::log(n_values / (support_end - support_start + 1.0))).unwrap()
+    }
+}
+
+impl<P: PrimInt, D> Sample for UniformNeighbourhood<P, D> {
+    type Param = P;
+
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        let (start, end) = self.support();
+        round_saturating(start + (rng.f64() * (end - start + 1.0)).floor())
+    }
+}
+
+impl<P, D> Kernel for UniformNeighbourhood<P, D>
+where
+    Self: Density<Param = P, Output = D> + Sample<Param = P>,
+    P: PrimInt,
+{
+    type Param = P;
+
+    fn new(location: P, std: P) -> Self {
+        assert!(std > P::zero());
+        Self::with_std(location, std.to_f64().unwrap())
+    }
+
+    /// Construct the kernel with the real-valued bandwidth as is, ignoring the bounds.
+    fn try_new_scaled(
+        location: P,
+        std: f64,
+        bounds: Option<RangeInclusive<P>>,
+    ) -> Result<Self, Error> {
+        let _ = bounds;
+        if std.is_finite() && std > 0.0 {
+            Ok(Self::with_std(location, std))
+        } else {
+            Err(Error::InvalidBandwidth)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn new_ok() {
+        assert_eq!(UniformNeighbourhood::<_, f64>::new(0, 1).radius, 1);
+        assert_eq!(UniformNeighbourhood::<_, f64>::new(0, 2).radius, 3);
+    }
+
+    #[test]
+    fn try_new_scaled_ok() {
+        let radius = |std| {
+            UniformNeighbourhood::<i32, f64>::try_new_scaled(0, std, None)
+                .unwrap()
+                .radius
+        };
+        assert_eq!(radius(0.5), 1);
+        assert_eq!(radius(2.9), 5);
+    }
+
+    #[test]
+    fn density_ok() {
+        let kernel = UniformNeighbourhood::<_, f64>::new(-10, 2);
+        assert_abs_diff_eq!(kernel.density(-14), 0.0);
+        assert_abs_diff_eq!(kernel.density(-13), 1.0 / 7.0);
+        assert_abs_diff_eq!(kernel.density(-7), 1.0 / 7.0);
+        assert_abs_diff_eq!(kernel.density(-6), 0.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn log_density_ok() {
+        let kernel = UniformNeighbourhood::<_, f64>::new(-10, 2);
+        assert_abs_diff_eq!(kernel.log_density(-13), -libm::log(7.0), epsilon = 1e-12);
+        assert_eq!(kernel.log_density(-6), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn log_mass_ok() {
+        let kernel = UniformNeighbourhood::<_, f64>::new(64, 1);
+        assert_abs_diff_eq!(kernel.log_mass(48..80), 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.log_mass(60..64), -libm::log(3.0), epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            (0..10)
+                .map(|i| kernel.log_mass(50 + i * 3..53 + i * 3).exp())
+                .sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+        assert_eq!(kernel.log_mass(66..80), f64::NEG_INFINITY);
+        assert_eq!(kernel.log_mass(64..64), f64::NEG_INFINITY);
+
+        let kernel = UniformNeighbourhood::<u8, f64>::new(254, 2);
+        assert_abs_diff_eq!(
+            kernel.log_mass(253..u8::MAX),
+            libm::log(0.4),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn sample_ok() {
+        let kernel = UniformNeighbourhood::<u16, f64>::new(2, 2);
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..100 {
+            assert!((0..=5).contains(&kernel.sample(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn near_bounds_ok() {
+        // The neighbourhood `251..=257` gets clipped by the type maximum:
+        let kernel = UniformNeighbourhood::<u8, f64>::new(254, 2);
+        assert_abs_diff_eq!(kernel.density(250), 0.0);
+        assert_abs_diff_eq!(kernel.density(251), 0.2);
+        assert_abs_diff_eq!(kernel.density(255), 0.2);
+
+        let mut rng = Rng::with_seed(42);
+        let n_maxima = (0..10_000)
+            .filter(|_| kernel.sample(&mut rng) == u8::MAX)
+            .count();
+        assert!((1_800..=2_200).contains(&n_maxima));
+    }
+}
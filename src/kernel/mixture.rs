@@ -0,0 +1,272 @@
+//! Weighted mixtures of differently typed kernels.
+
+use core::ops::Range;
+
+use fastrand::Rng;
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+
+use crate::{
+    iter::log_sum_exp,
+    kernel::Cdf,
+    traits::ops::{Arithmetic, Exp, Ln},
+    Density,
+    Sample,
+};
+
+/// [Mixture distribution][1] of differently typed components with explicit weights.
+///
+/// The components are stored as a tuple of `(weight, kernel)` pairs, so that, for example,
+/// a prior may put 80% of its mass around a known default and keep 20% uniform.
+/// The weights do not have to sum up to one, they get normalized.
+///
+/// Mixtures of two to six components are supported. To combine more, nest the mixtures.
+///
+/// # Example
+///
+/// ```rust
+/// use hyperopt::{
+///     kernel::{continuous::Gaussian, mixture::Mixture, universal::Uniform, Kernel},
+///     Density,
+/// };
+///
+/// let prior = Mixture::new((
+///     (0.8, Gaussian::new(1.0, 0.1)),
+///     (0.2, Uniform::<f64, f64>::with_bounds(0.0..=10.0)),
+/// ));
+/// assert!(prior.density(1.0) > prior.density(5.0));
+/// ```
+///
+/// [1]: https://en.wikipedia.org/wiki/Mixture_distribution
+#[derive(Copy, Clone, Debug)]
+pub struct Mixture<C> {
+    components: C,
+
+    /// Sum of the component weights.
+    total_weight: f64,
+}
+
+impl<C: Components> Mixture<C> {
+    /// Construct the mixture from the tuple of `(weight, kernel)` pairs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a weight is negative or not finite, or if all the weights are zero.
+    pub fn new(components: C) -> Self {
+        assert!(
+            components
+                .weights()
+                .all(|weight| weight.is_finite() && weight >= 0.0),
+            "weights should be finite and non-negative",
+        );
+        let total_weight = components.weights().sum();
+        assert!(total_weight > 0.0, "at least one weight should be positive");
+        Self {
+            components,
+            total_weight,
+        }
+    }
+}
+
+/// Tuple of `(weight, kernel)` pairs, which may form a [`Mixture`].
+pub trait Components {
+    /// Iterate over the component weights.
+    fn weights(&self) -> impl Iterator<Item = f64>;
+}
+
+macro_rules! impl_mixture {
+    ($($kernel:ident: $index:tt),+; $last_kernel:ident: $last_index:tt) => {
+        impl<$($kernel,)+ $last_kernel> Components for ($((f64, $kernel),)+ (f64, $last_kernel)) {
+            fn weights(&self) -> impl Iterator<Item = f64> {
+                [$(self.$index.0,)+ self.$last_index.0].into_iter()
+            }
+        }
+
+        impl<P, D, $($kernel,)+ $last_kernel> Density
+            for Mixture<($((f64, $kernel),)+ (f64, $last_kernel))>
+        where
+            $($kernel: Density<Param = P, Output = D>,)+
+            $last_kernel: Density<Param = P, Output = D>,
+            P: Copy,
+            D: Copy + Arithmetic + PartialOrd + Exp + FromPrimitive + Zero + One,
+        {
+            type Param = P;
+            type Output = D;
+
+            /// Calculate the weighted sum of the component densities.
+            fn density(&self, at: Self::Param) -> Self::Output {
+                let weighted = |weight: f64, density: D| {
+                    D::from_f64(weight / self.total_weight).unwrap() * density
+                };
+                D::zero()
+                    $(+ weighted(self.components.$index.0, self.components.$index.1.density(at)))+
+                    + weighted(
+                        self.components.$last_index.0,
+                        self.components.$last_index.1.density(at),
+                    )
+            }
+
+            /// Calculate the log-density as the log-sum-exp `ln Σ exp(xᵢ)`
+            /// of the weighted component log-densities.
+            fn log_density(&self, at: Self::Param) -> Self::Output
+            where
+                Self::Output: Ln,
+            {
+                let weighted = |weight: f64, log_density: D| {
+                    D::from_f64(weight / self.total_weight).unwrap().ln() + log_density
+                };
+                log_sum_exp([
+                    $(weighted(self.components.$index.0, self.components.$index.1.log_density(at)),)+
+                    weighted(
+                        self.components.$last_index.0,
+                        self.components.$last_index.1.log_density(at),
+                    ),
+                ])
+            }
+
+            /// Calculate the log-mass as the log-sum-exp `ln Σ exp(xᵢ)`
+            /// of the weighted component log-masses.
+            fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+            where
+                Self::Param: Copy + ToPrimitive + FromPrimitive,
+                Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+            {
+                let weighted = |weight: f64, log_mass: D| {
+                    D::from_f64(weight / self.total_weight).unwrap().ln() + log_mass
+                };
+                log_sum_exp([
+                    $(weighted(
+                        self.components.$index.0,
+                        self.components.$index.1.log_mass(interval.clone()),
+                    ),)+
+                    weighted(
+                        self.components.$last_index.0,
+                        self.components.$last_index.1.log_mass(interval),
+                    ),
+                ])
+            }
+        }
+
+        impl<P, D, $($kernel,)+ $last_kernel> Cdf
+            for Mixture<($((f64, $kernel),)+ (f64, $last_kernel))>
+        where
+            $($kernel: Cdf<Param = P, Output = D>,)+
+            $last_kernel: Cdf<Param = P, Output = D>,
+            P: Copy,
+            D: Copy + Arithmetic + FromPrimitive + Zero,
+        {
+            type Param = P;
+            type Output = D;
+
+            /// Calculate the weighted sum of the component CDFs.
+            fn cdf(&self, at: Self::Param) -> Self::Output {
+                let weighted = |weight: f64, cdf: D| {
+                    D::from_f64(weight / self.total_weight).unwrap() * cdf
+                };
+                D::zero()
+                    $(+ weighted(self.components.$index.0, self.components.$index.1.cdf(at)))+
+                    + weighted(self.components.$last_index.0, self.components.$last_index.1.cdf(at))
+            }
+        }
+
+        impl<P, $($kernel,)+ $last_kernel> Sample
+            for Mixture<($((f64, $kernel),)+ (f64, $last_kernel))>
+        where
+            $($kernel: Sample<Param = P>,)+
+            $last_kernel: Sample<Param = P>,
+        {
+            type Param = P;
+
+            /// Pick a component with the probability proportional to its weight,
+            /// and sample from it.
+            #[allow(unused_assignments)]
+            fn sample(&self, rng: &mut Rng) -> Self::Param {
+                let mut remaining = rng.f64() * self.total_weight;
+                $(
+                    if remaining < self.components.$index.0 {
+                        return self.components.$index.1.sample(rng);
+                    }
+                    remaining -= self.components.$index.0;
+                )+
+                // The last component also catches the rounding errors:
+                self.components.$last_index.1.sample(rng)
+            }
+        }
+    };
+}
+
+impl_mixture!(K1: 0; K2: 1);
+impl_mixture!(K1: 0, K2: 1; K3: 2);
+impl_mixture!(K1: 0, K2: 1, K3: 2; K4: 3);
+impl_mixture!(K1: 0, K2: 1, K3: 2, K4: 3; K5: 4);
+impl_mixture!(K1: 0, K2: 1, K3: 2, K4: 3, K5: 4; K6: 5);
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::kernel::{continuous::Gaussian, universal::Uniform, Kernel};
+
+    #[test]
+    fn density_ok() {
+        let mixture = Mixture::new((
+            (4.0, Gaussian::new(0.0, 1.0)),
+            (1.0, Uniform::<f64, f64>::with_bounds(0.0..=10.0)),
+        ));
+        assert_abs_diff_eq!(
+            mixture.density(0.0),
+            0.8f64.mul_add(0.398_942_280_401_432_7, 0.2 * 0.1),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            mixture.log_density(0.0),
+            mixture.density(0.0).ln(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn cdf_ok() {
+        let mixture = Mixture::new((
+            (0.5, Gaussian::new(0.0, 1.0)),
+            (0.25, Gaussian::new(10.0, 1.0)),
+            (0.25, Uniform::<f64, f64>::with_bounds(0.0..=10.0)),
+        ));
+        assert_abs_diff_eq!(mixture.cdf(5.0), 0.5 + 0.125, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn sample_ok() {
+        let mixture = Mixture::new((
+            (0.8, Uniform::<f64, f64>::with_bounds(0.0..=1.0)),
+            (0.2, Uniform::<f64, f64>::with_bounds(10.0..=11.0)),
+        ));
+        let mut rng = Rng::with_seed(42);
+        let n_low = (0..10_000)
+            .filter(|_| mixture.sample(&mut rng) < 5.0)
+            .count();
+        assert!((7_800..8_200).contains(&n_low), "n_low: {n_low}");
+    }
+
+    #[test]
+    fn zero_weight_ok() {
+        let mixture = Mixture::new((
+            (1.0, Uniform::<f64, f64>::with_bounds(0.0..=1.0)),
+            (0.0, Uniform::<f64, f64>::with_bounds(10.0..=11.0)),
+        ));
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..100 {
+            assert!(mixture.sample(&mut rng) <= 1.0);
+        }
+        assert_abs_diff_eq!(mixture.log_density(0.5), 0.0);
+    }
+
+    #[test]
+    #[should_panic = "at least one weight should be positive"]
+    fn all_zero_weights_panics() {
+        let _ = Mixture::new((
+            (0.0, Gaussian::new(0.0, 1.0)),
+            (0.0, Gaussian::new(1.0, 1.0)),
+        ));
+    }
+}
@@ -0,0 +1,345 @@
+//! Truncated kernels.
+
+use core::ops::{Range, RangeInclusive};
+
+use fastrand::Rng;
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+
+use crate::{
+    kernel::{Cdf, Kernel, Quantile},
+    traits::ops::{Additive, Arithmetic, Countable, Exp, Ln, Multiplicative},
+    Density,
+    Error,
+    Sample,
+};
+
+/// [Truncated][1] kernel: its density is zero outside the bounds, and renormalized inside.
+///
+/// Unlike clamping, truncation does not pile the probability mass up on the bounds,
+/// so that the density stays consistent with what is sampled. For the [countable][Countable]
+/// parameters, the bounds themselves keep their probability masses.
+///
+/// [`crate::Optimizer`] constructs it with the search range as the bounds. When constructed
+/// via [`Kernel::new`], or when the inner kernel has no mass within the bounds,
+/// it behaves exactly like the inner kernel.
+///
+/// [1]: https://en.wikipedia.org/wiki/Truncated_distribution
+#[derive(Copy, Clone, Debug)]
+pub struct Truncated<K: Cdf> {
+    kernel: K,
+    bounds: Option<Bounds<K::Param, K::Output>>,
+}
+
+/// Inclusive bounds and the inner kernel's probabilities around them.
+#[derive(Copy, Clone, Debug)]
+struct Bounds<P, D> {
+    min: P,
+    max: P,
+
+    /// Inner kernel's probability below the lower bound.
+    below: D,
+
+    /// Inner kernel's probability mass within the bounds.
+    mass: D,
+}
+
+impl<K> Truncated<K>
+where
+    K: Cdf,
+    K::Param: Copy + Countable,
+    K::Output: Copy + Additive + PartialOrd + Zero,
+{
+    /// Truncate the kernel to the **inclusive** bounds.
+    pub fn with_bounds(kernel: K, bounds: RangeInclusive<K::Param>) -> Self {
+        let (min, max) = bounds.into_inner();
+
+        // The left limit of the CDF, so that the mass at the lower bound stays within the bounds:
+        let below = match min.predecessor() {
+            Some(predecessor) => kernel.cdf(predecessor),
+            None if K::Param::n_values(&(min..=min)).is_some() => K::Output::zero(),
+            None => kernel.cdf(min),
+        };
+        let mass = kernel.cdf(max) - below;
+        Self {
+            kernel,
+            bounds: (mass > K::Output::zero()).then_some(Bounds {
+                min,
+                max,
+                below,
+                mass,
+            }),
+        }
+    }
+}
+
+impl<K, P, D> Density for Truncated<K>
+where
+    K: Density<Param = P, Output = D> + Cdf<Param = P, Output = D>,
+    P: Copy + PartialOrd + Countable,
+    D: Copy + Arithmetic + Zero,
+{
+    type Param = P;
+    type Output = D;
+
+    fn density(&self, at: Self::Param) -> Self::Output {
+        match self.bounds {
+            Some(Bounds { min, max, mass, .. }) if (min..=max).contains(&at) => {
+                self.kernel.density(at) / mass
+            }
+            Some(_) => D::zero(),
+            None => self.kernel.density(at),
+        }
+    }
+
+    fn log_density(&self, at: Self::Param) -> Self::Output
+    where
+        Self::Output: Ln,
+    {
+        match self.bounds {
+            Some(Bounds { min, max, mass, .. }) if (min..=max).contains(&at) => {
+                self.kernel.log_density(at) - mass.ln()
+            }
+            Some(_) => D::zero().ln(),
+            None => self.kernel.log_density(at),
+        }
+    }
+
+    /// Calculate the inner kernel's log-mass within the interval, clamped to the bounds.
+    ///
+    /// For the countable parameters, the interval end is clamped to the value next to
+    /// the upper bound, so that the latter stays within the interval.
+    fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+    where
+        Self::Param: Copy + ToPrimitive + FromPrimitive,
+        Self::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        match self.bounds {
+            Some(Bounds { min, max, mass, .. }) => {
+                let clamp = |at| num_traits::clamp(at, min, max);
+                let end = if interval.end > max {
+                    max.successor().unwrap_or(max)
+                } else {
+                    clamp(interval.end)
+                };
+                self.kernel.log_mass(clamp(interval.start)..end) - mass.ln()
+            }
+            None => self.kernel.log_mass(interval),
+        }
+    }
+}
+
+impl<K, P, D> Cdf for Truncated<K>
+where
+    K: Cdf<Param = P, Output = D>,
+    P: Copy + PartialOrd,
+    D: Copy + Additive + Multiplicative + Zero + One,
+{
+    type Param = P;
+    type Output = D;
+
+    fn cdf(&self, at: Self::Param) -> Self::Output {
+        match self.bounds {
+            Some(Bounds { min, .. }) if at < min => D::zero(),
+            Some(Bounds { max, .. }) if at >= max => D::one(),
+            Some(Bounds { below, mass, .. }) => (self.kernel.cdf(at) - below) / mass,
+            None => self.kernel.cdf(at),
+        }
+    }
+}
+
+impl<K, P, D> Quantile for Truncated<K>
+where
+    K: Cdf<Param = P, Output = D> + Quantile<Param = P, Probability = D>,
+    P: Copy + PartialOrd,
+    D: Copy + Arithmetic,
+{
+    type Param = P;
+    type Probability = D;
+
+    /// Map the probability into the inner kernel's CDF range within the bounds,
+    /// and invert the inner kernel's CDF.
+    fn quantile(&self, probability: Self::Probability) -> Self::Param {
+        match self.bounds {
+            Some(Bounds {
+                min,
+                max,
+                below,
+                mass,
+            }) => {
+                let quantile = self.kernel.quantile(below + probability * mass);
+                // Guard against the rounding errors:
+                num_traits::clamp(quantile, min, max)
+            }
+            None => self.kernel.quantile(probability),
+        }
+    }
+}
+
+impl<K, P, D> Sample for Truncated<K>
+where
+    K: Cdf<Param = P, Output = D> + Quantile<Param = P, Probability = D> + Sample<Param = P>,
+    P: Copy + PartialOrd,
+    D: Copy + Arithmetic + FromPrimitive,
+{
+    type Param = P;
+
+    /// Generate a sample using the [inverse transform sampling][1].
+    ///
+    /// Unlike the rejection sampling, it takes constant time regardless of how little mass
+    /// the inner kernel has within the bounds.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Inverse_transform_sampling
+    fn sample(&self, rng: &mut Rng) -> Self::Param {
+        if self.bounds.is_some() {
+            self.quantile(D::from_f64(rng.f64()).unwrap())
+        } else {
+            self.kernel.sample(rng)
+        }
+    }
+}
+
+impl<K, P> Kernel for Truncated<K>
+where
+    Self: Density<Param = P> + Sample<Param = P>,
+    K: Kernel<Param = P> + Cdf<Param = P>,
+    K::Output: Copy + Additive + PartialOrd + Zero,
+    P: Copy + Countable,
+{
+    type Param = P;
+
+    fn new(location: P, std: P) -> Self {
+        Self {
+            kernel: K::new(location, std),
+            bounds: None,
+        }
+    }
+
+    fn new_bounded(location: P, std: P, bounds: RangeInclusive<P>) -> Self {
+        Self::with_bounds(K::new(location, std), bounds)
+    }
+
+    fn try_new(location: P, std: P) -> Result<Self, Error>
+    where
+        P: PartialOrd + Zero,
+    {
+        Ok(Self {
+            kernel: K::try_new(location, std)?,
+            bounds: None,
+        })
+    }
+
+    fn try_new_bounded(location: P, std: P, bounds: RangeInclusive<P>) -> Result<Self, Error>
+    where
+        P: PartialOrd + Zero,
+    {
+        Ok(Self::with_bounds(K::try_new(location, std)?, bounds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::kernel::{
+        continuous::{Epanechnikov, Gaussian},
+        universal::Uniform,
+    };
+
+    #[test]
+    fn density_ok() {
+        let kernel = Truncated::with_bounds(Gaussian::default(), 0.0..=10.0);
+        assert_abs_diff_eq!(kernel.density(-0.1), 0.0);
+        assert_abs_diff_eq!(kernel.density(0.0), 0.797_884_560_802_865_4, epsilon = 1e-9);
+        assert_abs_diff_eq!(kernel.density(11.0), 0.0);
+    }
+
+    #[test]
+    fn cdf_ok() {
+        let kernel = Truncated::with_bounds(Epanechnikov::default(), 0.0..=10.0);
+        assert_abs_diff_eq!(kernel.cdf(-1.0), 0.0);
+        assert_abs_diff_eq!(kernel.cdf(0.0), 0.0);
+        assert_abs_diff_eq!(kernel.cdf(f64::sqrt(5.0)), 1.0);
+        assert_abs_diff_eq!(kernel.cdf(11.0), 1.0);
+    }
+
+    #[test]
+    fn log_mass_ok() {
+        let kernel = Truncated::with_bounds(Epanechnikov::default(), 0.0..=10.0);
+        assert_abs_diff_eq!(kernel.log_mass(-1.0..11.0), 0.0);
+        assert_abs_diff_eq!(
+            kernel.log_mass(0.0..f64::sqrt(5.0) / 2.0),
+            0.6875_f64.ln(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn sample_ok() {
+        let kernel = Truncated::<Epanechnikov<f64>>::new_bounded(0.0, 1.0, 0.0..=1.0);
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..100 {
+            assert!((0.0..=1.0).contains(&kernel.sample(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn quantile_ok() {
+        let kernel = Truncated::with_bounds(Gaussian::default(), 0.0..=10.0);
+        assert_abs_diff_eq!(kernel.quantile(0.0), 0.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            kernel.quantile(0.5),
+            0.674_489_750_196_081_7,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(kernel.cdf(kernel.quantile(0.9)), 0.9, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn sample_far_tail_ok() {
+        // Rejection sampling would almost never hit the bounds:
+        let kernel = Truncated::with_bounds(Gaussian::default(), 6.0..=7.0);
+        let mut rng = Rng::with_seed(42);
+        for _ in 0..100 {
+            assert!((6.0..=7.0).contains(&kernel.sample(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn discrete_ok() {
+        // The bounds keep their masses:
+        let kernel = Truncated::with_bounds(Uniform::<i32, f64>::with_bounds(0..=9), 3..=5);
+        assert_abs_diff_eq!(
+            (0..=9).map(|at| kernel.density(at)).sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(kernel.density(3), 1.0 / 3.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.cdf(3), 1.0 / 3.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.log_mass(0..10), 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.log_mass(5..10), -libm::log(3.0), epsilon = 1e-12);
+
+        let mut rng = Rng::with_seed(42);
+        let mut counts = [0; 3];
+        for _ in 0..3_000 {
+            counts[usize::try_from(kernel.sample(&mut rng) - 3).unwrap()] += 1;
+        }
+        assert!(
+            counts.iter().all(|count| (900..=1_100).contains(count)),
+            "{counts:?}"
+        );
+    }
+
+    #[test]
+    fn discrete_type_bounds_ok() {
+        let kernel = Truncated::with_bounds(Uniform::<u8, f64>::with_bounds(0..=3), 0..=u8::MAX);
+        assert_abs_diff_eq!(kernel.density(0), 0.25, epsilon = 1e-12);
+        assert_abs_diff_eq!(kernel.log_mass(0..1), -libm::log(4.0), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn no_mass_ok() {
+        let kernel = Truncated::with_bounds(Epanechnikov::default(), 10.0..=20.0);
+        assert_abs_diff_eq!(kernel.density(0.0), 0.335_410_196_624_968_46);
+    }
+}
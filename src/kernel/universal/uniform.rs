@@ -1,11 +1,15 @@
-use std::{marker::PhantomData, ops::RangeInclusive};
+use core::{
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+};
 
 use fastrand::Rng;
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
 
 use crate::{
-    constants::{ConstDoubleSqrt3, ConstSqrt3},
-    kernel::Kernel,
-    traits::ops::{Additive, MulAdd, Multiplicative, Neg},
+    constants::ConstSqrt3,
+    kernel::{Cdf, Kernel, Quantile},
+    traits::ops::{Arithmetic, Exp, Ln, MulAdd, Neg},
     Density,
     Sample,
 };
@@ -35,25 +39,61 @@ where
     }
 }
 
-impl<P, D> Density for Uniform<P, D>
-where
-    P: Copy + Into<D> + PartialOrd + Additive,
-    D: Multiplicative + num_traits::Zero + ConstDoubleSqrt3,
-{
-    type Param = P;
-    type Output = D;
+macro_rules! impl_sample_discrete {
+    ($type:ident) => {
+        impl<D: FromPrimitive> Density for Uniform<$type, D> {
+            type Param = $type;
+            type Output = D;
+
+            /// Calculate the probability mass: each value in the range is equally probable.
+            #[allow(clippy::cast_lossless, clippy::cast_precision_loss)]
+            fn density(&self, at: Self::Param) -> Self::Output {
+                let density = if (self.min..=self.max).contains(&at) {
+                    1.0 / (self.min.abs_diff(self.max) as f64 + 1.0)
+                } else {
+                    0.0
+                };
+                D::from_f64(density).unwrap()
+            }
+
+            /// Calculate the probability mass of the values within `[start, end)`.
+            #[allow(clippy::cast_lossless, clippy::cast_precision_loss)]
+            fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+            where
+                Self::Param: Copy + ToPrimitive + FromPrimitive,
+                Self::Output:
+                    Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+            {
+                let start = interval.start.max(self.min);
+                let mass = if start < interval.end && start <= self.max {
+                    // The interval is not empty, so the end is always above the minimum:
+                    let last = (interval.end - 1).min(self.max);
+                    (start.abs_diff(last) as f64 + 1.0) / (self.min.abs_diff(self.max) as f64 + 1.0)
+                } else {
+                    0.0
+                };
+                D::from_f64(mass).unwrap().ln()
+            }
+        }
 
-    fn density(&self, at: Self::Param) -> Self::Output {
-        if (self.min..=self.max).contains(&at) {
-            (self.max - self.min).into() / D::DOUBLE_SQRT_3
-        } else {
-            D::zero()
+        impl<D: FromPrimitive> Cdf for Uniform<$type, D> {
+            type Param = $type;
+            type Output = D;
+
+            #[allow(clippy::cast_lossless, clippy::cast_precision_loss)]
+            fn cdf(&self, at: Self::Param) -> Self::Output {
+                let cdf = if at < self.min {
+                    0.0
+                } else if at >= self.max {
+                    1.0
+                } else {
+                    (self.min.abs_diff(at) as f64 + 1.0)
+                        / (self.min.abs_diff(self.max) as f64 + 1.0)
+                };
+                D::from_f64(cdf).unwrap()
+            }
         }
-    }
-}
 
-macro_rules! impl_sample_discrete {
-    ($type:ident) => {
         impl<D> Sample for Uniform<$type, D> {
             type Param = $type;
 
@@ -61,6 +101,24 @@ macro_rules! impl_sample_discrete {
                 rng.$type(self.min..=self.max)
             }
         }
+
+        impl<D: ToPrimitive> Quantile for Uniform<$type, D> {
+            type Param = $type;
+            type Probability = D;
+
+            /// Find the value, assigning each one an equally wide bin of probability.
+            #[allow(
+                clippy::cast_lossless,
+                clippy::cast_possible_truncation,
+                clippy::cast_precision_loss,
+                clippy::cast_sign_loss
+            )]
+            fn quantile(&self, probability: Self::Probability) -> Self::Param {
+                let n_values = self.min.abs_diff(self.max) as f64 + 1.0;
+                let offset = libm::floor(probability.to_f64().unwrap() * n_values);
+                (self.min as f64 + offset).min(self.max as f64) as $type
+            }
+        }
     };
 }
 
@@ -77,8 +135,59 @@ impl_sample_discrete!(u64);
 impl_sample_discrete!(i128);
 impl_sample_discrete!(u128);
 
+macro_rules! impl_density_continuous {
+    ($type:ty) => {
+        impl<D: FromPrimitive> Density for Uniform<$type, D> {
+            type Param = $type;
+            type Output = D;
+
+            fn density(&self, at: Self::Param) -> Self::Output {
+                let density = if (self.min..=self.max).contains(&at) {
+                    1.0 / (self.max - self.min).to_f64().unwrap()
+                } else {
+                    0.0
+                };
+                D::from_f64(density).unwrap()
+            }
+
+            fn log_mass(&self, interval: Range<Self::Param>) -> Self::Output
+            where
+                Self::Param: Copy + ToPrimitive + FromPrimitive,
+                Self::Output:
+                    Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+            {
+                (self.cdf(interval.end) - self.cdf(interval.start)).ln()
+            }
+        }
+
+        impl<D: FromPrimitive> Cdf for Uniform<$type, D> {
+            type Param = $type;
+            type Output = D;
+
+            fn cdf(&self, at: Self::Param) -> Self::Output {
+                let cdf = if at < self.min {
+                    0.0
+                } else if at >= self.max {
+                    1.0
+                } else {
+                    (at - self.min).to_f64().unwrap() / (self.max - self.min).to_f64().unwrap()
+                };
+                D::from_f64(cdf).unwrap()
+            }
+        }
+    };
+}
+
 macro_rules! impl_sample_continuous {
     ($type:ident) => {
+        impl_density_continuous!($type);
+
+        #[cfg(feature = "ordered-float")]
+        impl_density_continuous!(ordered_float::OrderedFloat<$type>);
+
+        #[cfg(feature = "ordered-float")]
+        impl_density_continuous!(ordered_float::NotNan<$type>);
+
         impl<D> Sample for Uniform<$type, D> {
             type Param = $type;
 
@@ -87,13 +196,41 @@ macro_rules! impl_sample_continuous {
             }
         }
 
+        impl<D: ToPrimitive> Quantile for Uniform<$type, D> {
+            type Param = $type;
+            type Probability = D;
+
+            #[allow(clippy::cast_possible_truncation)]
+            fn quantile(&self, probability: Self::Probability) -> Self::Param {
+                (probability.to_f64().unwrap() as $type).mul_add(self.max - self.min, self.min)
+            }
+        }
+
         #[cfg(feature = "ordered-float")]
         impl<D> Sample for Uniform<ordered_float::OrderedFloat<$type>, D> {
             type Param = ordered_float::OrderedFloat<$type>;
 
             fn sample(&self, rng: &mut Rng) -> ordered_float::OrderedFloat<$type> {
-                use num_traits::Float;
-                ordered_float::OrderedFloat(rng.$type()).mul_add(self.max - self.min, self.min)
+                num_traits::Float::mul_add(
+                    ordered_float::OrderedFloat(rng.$type()),
+                    self.max - self.min,
+                    self.min,
+                )
+            }
+        }
+
+        #[cfg(feature = "ordered-float")]
+        impl<D: ToPrimitive> Quantile for Uniform<ordered_float::OrderedFloat<$type>, D> {
+            type Param = ordered_float::OrderedFloat<$type>;
+            type Probability = D;
+
+            #[allow(clippy::cast_possible_truncation)]
+            fn quantile(&self, probability: Self::Probability) -> Self::Param {
+                num_traits::Float::mul_add(
+                    ordered_float::OrderedFloat(probability.to_f64().unwrap() as $type),
+                    self.max - self.min,
+                    self.min,
+                )
             }
         }
 
@@ -106,6 +243,19 @@ macro_rules! impl_sample_continuous {
                 normalized * (self.max - self.min) + self.min
             }
         }
+
+        #[cfg(feature = "ordered-float")]
+        impl<D: ToPrimitive> Quantile for Uniform<ordered_float::NotNan<$type>, D> {
+            type Param = ordered_float::NotNan<$type>;
+            type Probability = D;
+
+            #[allow(clippy::cast_possible_truncation)]
+            fn quantile(&self, probability: Self::Probability) -> Self::Param {
+                let normalized = ordered_float::NotNan::new(probability.to_f64().unwrap() as $type)
+                    .expect("probability should not be NaN");
+                normalized * (self.max - self.min) + self.min
+            }
+        }
     };
 }
 
@@ -128,3 +278,71 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn density_discrete_ok() {
+        let kernel: Uniform<_, f64> = Uniform::with_bounds(-1..=2);
+        assert_abs_diff_eq!(kernel.density(-2), 0.0);
+        assert_abs_diff_eq!(kernel.density(-1), 0.25);
+        assert_abs_diff_eq!(kernel.density(2), 0.25);
+        assert_abs_diff_eq!(kernel.density(3), 0.0);
+    }
+
+    #[test]
+    fn density_continuous_ok() {
+        let kernel: Uniform<_, f64> = Uniform::with_bounds(-1.0..=3.0);
+        assert_abs_diff_eq!(kernel.density(-1.5), 0.0);
+        assert_abs_diff_eq!(kernel.density(0.0), 0.25);
+        assert_abs_diff_eq!(kernel.density(3.5), 0.0);
+    }
+
+    #[test]
+    fn cdf_discrete_ok() {
+        let kernel: Uniform<_, f64> = Uniform::with_bounds(-1..=2);
+        assert_abs_diff_eq!(kernel.cdf(-2), 0.0);
+        assert_abs_diff_eq!(kernel.cdf(-1), 0.25);
+        assert_abs_diff_eq!(kernel.cdf(0), 0.5);
+        assert_abs_diff_eq!(kernel.cdf(2), 1.0);
+        assert_abs_diff_eq!(kernel.cdf(3), 1.0);
+    }
+
+    #[test]
+    fn cdf_continuous_ok() {
+        let kernel: Uniform<_, f64> = Uniform::with_bounds(-1.0..=3.0);
+        assert_abs_diff_eq!(kernel.cdf(-1.5), 0.0);
+        assert_abs_diff_eq!(kernel.cdf(0.0), 0.25);
+        assert_abs_diff_eq!(kernel.cdf(3.5), 1.0);
+    }
+
+    #[test]
+    fn quantile_discrete_ok() {
+        let kernel: Uniform<_, f64> = Uniform::with_bounds(-1..=1);
+        assert_eq!(kernel.quantile(0.0), -1);
+        assert_eq!(kernel.quantile(0.3), -1);
+        assert_eq!(kernel.quantile(0.5), 0);
+        assert_eq!(kernel.quantile(0.7), 1);
+        assert_eq!(kernel.quantile(1.0), 1);
+    }
+
+    #[test]
+    fn quantile_discrete_full_range_ok() {
+        let kernel: Uniform<_, f64> = Uniform::with_bounds(i8::MIN..=i8::MAX);
+        assert_eq!(kernel.quantile(0.0), i8::MIN);
+        assert_eq!(kernel.quantile(1.0), i8::MAX);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn quantile_continuous_ok() {
+        let kernel: Uniform<_, f64> = Uniform::with_bounds(-1.0..=3.0);
+        assert_eq!(kernel.quantile(0.0), -1.0);
+        assert_eq!(kernel.quantile(0.25), 0.0);
+        assert_eq!(kernel.quantile(1.0), 3.0);
+    }
+}
@@ -1,14 +1,37 @@
 #![doc = include_str!("../README.md")]
+#![no_std]
 
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+pub mod design;
+mod error;
 mod iter;
 pub mod kde;
 pub mod kernel;
 mod optimizer;
+pub mod quantization;
 mod range;
+pub mod space;
 mod traits;
 
+#[cfg(feature = "derive")]
+pub use hyperopt_derive::SearchSpace;
+
 pub use self::{
-    kernel::{Density, Sample},
-    optimizer::Optimizer,
-    traits::constants,
+    error::Error,
+    kernel::{Cdf, Density, Quantile, Sample},
+    optimizer::{BandwidthBound, Optimizer, OptimizerBuilder, Trial, TrialHandle, TrialMetadata},
+    space::SearchSpace,
+    traits::{constants, ops},
 };
+
+/// Re-exports for the generated code. Not a public API.
+#[doc(hidden)]
+#[cfg(feature = "derive")]
+pub mod __private {
+    pub use fastrand::Rng;
+    // The generated code may be compiled without `std`, where `f64` has no `ln()` and friends:
+    pub use libm::{exp, log as ln, round};
+}
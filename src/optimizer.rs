@@ -1,21 +1,42 @@
-use std::{fmt::Debug, iter, ops::RangeInclusive};
+use alloc::{boxed::Box, collections::BTreeMap, vec, vec::Vec};
+use core::{
+    fmt::Debug,
+    iter,
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+};
 
 use fastrand::Rng;
-use num_traits::{FromPrimitive, One, Zero};
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
 
 use crate::{
-    iter::{Triple, Triples},
+    design::Design,
+    iter::{log_sum_exp, Triple, Triples},
     kde::KernelDensityEstimator,
-    kernel::Kernel,
-    optimizer::trial::{Trial, Trials},
+    kernel::{universal::Uniform, Kernel},
+    optimizer::trial::Trials,
+    quantization::Quantization,
     range::CopyRange,
-    traits::ops::Arithmetic,
+    traits::ops::{Additive, Arithmetic, Countable, Exp, Ln, Rem, TotalOrd},
     Density,
+    Error,
+    Quantile,
     Sample,
 };
 
+mod bandwidth;
+mod builder;
 mod trial;
 
+use self::bandwidth::Scale;
+pub use self::{
+    bandwidth::BandwidthBound,
+    builder::OptimizerBuilder,
+    trial::{Trial, TrialHandle, TrialMetadata},
+};
+
 /// ✨ Hyperparameter optimizer.
 ///
 /// # Generic parameters
@@ -23,105 +44,275 @@ mod trial;
 /// - [`KInit`]: kernel type of the initial (prior) estimator component
 /// - [`P`]: type of parameter that is optimized
 /// - [`M`]: value of the target function, the less – the better
+/// - [`K`]: kernel type of the trial components, see [`OptimizerBuilder::kernel`]
 #[derive(Debug)]
-pub struct Optimizer<KInit, P, M> {
-    pub cutoff: f64,
-    pub n_candidates: usize,
-    pub bandwidth: P,
+pub struct Optimizer<KInit, P, M, K = ()> {
+    cutoff: f64,
+    n_candidates: usize,
+    max_rejections: usize,
+    exhaustive_limit: usize,
+
+    /// Bandwidth multiplier and the bounds, resolved in the parameter units.
+    scale: Scale,
+
+    allow_repeats: bool,
+    reflection: bool,
+    periodic: bool,
 
     range: RangeInclusive<P>,
     init_kernel: KInit,
+    initial_design: Option<Box<dyn Design + Send>>,
+    quantization: Option<Box<dyn Quantization<P> + Send>>,
     rng: Rng,
     good_trials: Trials<P, M>,
     bad_trials: Trials<P, M>,
+
+    /// Started, but not yet finished trials by their IDs.
+    pending_trials: BTreeMap<u64, P>,
+
+    /// ID of the next trial.
+    next_trial_id: u64,
+
+    kernel: PhantomData<fn() -> K>,
 }
 
 impl<KInit, P, M> Optimizer<KInit, P, M> {
-    /// Construct the new optimizer.
+    /// Start building the new optimizer.
     ///
     /// Here begins your adventure!
     ///
     /// # Parameters
     ///
-    /// - `range`: parameter search range, [`Optimizer`] will clamp random samples to this range
+    /// - `range`: parameter search range, see [`OptimizerBuilder::reflection`]
+    ///   and [`OptimizerBuilder::periodic`] for how the samples are kept within it
     /// - `init_kernel`: your prior belief about which values of the searched parameter is more optimal
-    /// - `kernel`: kernel for the trial components
+    ///
+    /// The trial kernel is to be chosen with [`OptimizerBuilder::kernel`].
+    pub const fn builder(
+        range: RangeInclusive<P>,
+        init_kernel: KInit,
+        rng: Rng,
+    ) -> OptimizerBuilder<KInit, P, M> {
+        OptimizerBuilder::new(range, init_kernel, rng)
+    }
+}
+
+impl<KInit, P, M, K> Optimizer<KInit, P, M, K> {
+    /// Construct the new optimizer with the default configuration.
+    ///
+    /// # Panics
+    ///
+    /// This function panics, if the search range is invalid, see [`OptimizerBuilder::try_build`].
+    #[deprecated(
+        since = "0.1.0",
+        note = "use `Optimizer::builder(…).kernel::<K>().build()` instead"
+    )]
     pub fn new(range: RangeInclusive<P>, init_kernel: KInit, rng: Rng) -> Self
     where
-        P: One,
+        P: Copy + PartialOrd + Additive + ToPrimitive + Countable,
     {
-        Self {
-            range,
-            init_kernel,
-            rng,
-            cutoff: 0.1,
-            n_candidates: 25,
-            bandwidth: P::one(),
-            good_trials: Trials::new(),
-            bad_trials: Trials::new(),
+        Optimizer::builder(range, init_kernel, rng).kernel().build()
+    }
+
+    /// Provide the information about the trial, or in other words, «fit» the optimizer on the sample.
+    ///
+    /// Normally, you'll call your target function on parameters supplied by [`Optimizer::new_trial`],
+    /// and feed back the results. But you also can feed it with any arbitrary parameters.
+    ///
+    /// NaN metric means a failed trial: it ranks as the worst one. Unless
+    /// [repeats are allowed][OptimizerBuilder::allow_repeats], a repeated parameter is ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `parameter`: the target function parameter
+    /// - `metric`: the target function metric
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the parameter is NaN, see [`Optimizer::try_feed_back`]
+    /// for the non-panicking version.
+    pub fn feed_back(&mut self, parameter: P, metric: M)
+    where
+        P: Copy + Debug + TotalOrd,
+        M: Debug + TotalOrd,
+    {
+        self.feed_back_with_metadata(parameter, metric, TrialMetadata::default());
+    }
+
+    /// Provide the information about the trial along with its [metadata][TrialMetadata].
+    ///
+    /// The metadata is retained in the history and does not affect the optimization.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the parameter is NaN, see [`Optimizer::try_feed_back_with_metadata`]
+    /// for the non-panicking version.
+    pub fn feed_back_with_metadata(&mut self, parameter: P, metric: M, metadata: TrialMetadata)
+    where
+        P: Copy + Debug + TotalOrd,
+        M: Debug + TotalOrd,
+    {
+        match self.try_feed_back_with_metadata(parameter, metric, metadata) {
+            Ok(()) | Err(Error::RepeatedParameter) => {}
+            Err(error) => panic!("failed to feed back `{parameter:?}`: {error}"),
         }
     }
 
-    /// Set the ratio of «good» trials.
-    #[must_use]
-    pub fn cutoff(mut self, cutoff: impl Into<f64>) -> Self {
-        self.cutoff = cutoff.into();
-        self
+    /// Provide the information about the trial, just like [`Optimizer::feed_back`],
+    /// but return an error instead of panicking or ignoring the parameter.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::NanParameter`]: the parameter is NaN
+    /// - [`Error::RepeatedParameter`]: the parameter has already been tried, and the trial
+    ///   has been ignored, see [`OptimizerBuilder::allow_repeats`]
+    pub fn try_feed_back(&mut self, parameter: P, metric: M) -> Result<(), Error>
+    where
+        P: Copy + Debug + TotalOrd,
+        M: Debug + TotalOrd,
+    {
+        self.try_feed_back_with_metadata(parameter, metric, TrialMetadata::default())
     }
 
-    /// Set the number of candidates to choose the next trial from the acquisition function[^1].
+    /// Provide the information about the trial, just like [`Optimizer::feed_back_with_metadata`],
+    /// but return an error instead of panicking or ignoring the parameter.
     ///
-    /// Sampling from the acquisition function is cheaper than evaluating the target cost function,
-    /// so the more candidates – the more effective is the optimization step.
+    /// With the `std` feature, the missing [finish timestamp][TrialMetadata::finished_at]
+    /// is set to the current time.
     ///
-    /// However, the acquisition function is an approximation of a potential gain,
-    /// so the fewer candidates – the more precise is the optimization step.
+    /// # Errors
     ///
-    /// The number of candidates is therefore a tradeoff.
+    /// See [`Optimizer::try_feed_back`].
+    pub fn try_feed_back_with_metadata(
+        &mut self,
+        parameter: P,
+        metric: M,
+        metadata: TrialMetadata,
+    ) -> Result<(), Error>
+    where
+        P: Copy + Debug + TotalOrd,
+        M: Debug + TotalOrd,
+    {
+        if parameter.is_nan() {
+            return Err(Error::NanParameter);
+        }
+        self.insert_trial(None, parameter, metric, metadata)
+    }
+
+    /// Finish the trial [started][Optimizer::start_trial] earlier, and feed its metric back.
+    ///
+    /// The trial keeps the ID and the metadata of the handle. With the `std` feature,
+    /// the missing [finish timestamp][TrialMetadata::finished_at] is set to the current time.
     ///
-    /// [^1]: Acquisition function is basically a ratio
-    ///       between the «good» KDE and «bad» KDE at the same point.
-    #[must_use]
-    pub fn n_candidates(mut self, n_candidates: impl Into<usize>) -> Self {
-        self.n_candidates = n_candidates.into();
-        self
+    /// # Errors
+    ///
+    /// - [`Error::UnknownTrial`]: the trial is not pending in this optimizer,
+    ///   for example, the handle has been issued by another optimizer
+    /// - [`Error::RepeatedParameter`]: the parameter has been fed back meanwhile,
+    ///   so the result has been ignored, see [`OptimizerBuilder::allow_repeats`]
+    pub fn finish_trial(&mut self, handle: TrialHandle<P>, metric: M) -> Result<(), Error>
+    where
+        P: Copy + Debug + TotalOrd,
+        M: Debug + TotalOrd,
+    {
+        if self.pending_trials.remove(&handle.id).is_none() {
+            return Err(Error::UnknownTrial { id: handle.id });
+        }
+        self.insert_trial(Some(handle.id), handle.parameter, metric, handle.metadata)
     }
 
-    /// Set the bandwidth multiplier for the estimator kernels.
+    /// Abandon the trial [started][Optimizer::start_trial] earlier, without feeding anything back.
     ///
-    /// Standard deviation of the kernel is the distance from the point to its furthest neighbour,
-    /// multiplied by this coefficient.
+    /// The trial is no longer pending, so that its parameter may be suggested again.
     ///
-    /// The default multiplier is [`P::one`]. Lower bandwidth approximates the density better,
-    /// however, is also prone to over-fitting. Higher bandwidth avoid over-fitting better,
-    /// but is also smoother and less precise.
-    #[must_use]
-    pub fn bandwidth(mut self, bandwidth: impl Into<P>) -> Self {
-        self.bandwidth = bandwidth.into();
-        self
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownTrial`], if the trial is not pending in this optimizer.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn abandon_trial(&mut self, handle: TrialHandle<P>) -> Result<(), Error> {
+        self.abandon_trial_by_id(handle.id).map(|_| ())
     }
 
-    /// Provide the information about the trial, or in other words, «fit» the optimizer on the sample.
+    /// Abandon the pending trial by its ID, for example, one of [`Optimizer::pending_trials`],
+    /// which has been lost along with its handle.
     ///
-    /// Normally, you'll call your target function on parameters supplied by [`Optimizer::new_trial`],
-    /// and feed back the results. But you also can feed it with any arbitrary parameters.
+    /// Returns the parameter of the abandoned trial.
     ///
-    /// # Parameters
+    /// # Errors
     ///
-    /// - `parameter`: the target function parameter
-    /// - `metric`: the target function metric
+    /// Returns [`Error::UnknownTrial`], if the trial is not pending in this optimizer.
+    pub fn abandon_trial_by_id(&mut self, id: u64) -> Result<P, Error> {
+        self.pending_trials
+            .remove(&id)
+            .ok_or(Error::UnknownTrial { id })
+    }
+
+    /// Iterate the started, but not yet finished trials as `(id, parameter)` pairs.
+    ///
+    /// A trial which stays pending for too long has likely been lost.
+    pub fn pending_trials(&self) -> impl Iterator<Item = (u64, P)> + '_
+    where
+        P: Copy,
+    {
+        self.pending_trials
+            .iter()
+            .map(|(id, parameter)| (*id, *parameter))
+    }
+
+    /// Allocate the next trial ID.
+    const fn next_trial_id(&mut self) -> u64 {
+        let id = self.next_trial_id;
+        self.next_trial_id += 1;
+        id
+    }
+
+    /// Insert the trial into the history, unless it repeats an already tried parameter.
+    ///
+    /// The trial keeps the ID of the [started][Optimizer::start_trial] trial, if any.
+    /// Otherwise, the next ID gets allocated, but only if the trial is inserted,
+    /// so that the IDs stay dense.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::RepeatedParameter`], if the trial has been ignored as a repeated one.
+    /// Even when repeats are allowed, a trial with the same parameter and metric is ignored.
     #[allow(
         clippy::cast_possible_truncation,
         clippy::cast_precision_loss,
         clippy::cast_sign_loss,
         clippy::missing_panics_doc
     )]
-    pub fn feed_back(&mut self, parameter: P, metric: M)
+    fn insert_trial(
+        &mut self,
+        id: Option<u64>,
+        parameter: P,
+        metric: M,
+        #[allow(unused_mut)] mut metadata: TrialMetadata,
+    ) -> Result<(), Error>
     where
-        P: Copy + Debug + Ord,
-        M: Debug + Ord,
+        P: Copy + Debug + TotalOrd,
+        M: Debug + TotalOrd,
     {
-        let trial = Trial { metric, parameter };
+        let parameter = self
+            .quantization
+            .as_ref()
+            .map_or(parameter, |quantization| quantization.snap(parameter));
+        if !self.allow_repeats
+            && (self.good_trials.contains(&parameter) || self.bad_trials.contains(&parameter))
+        {
+            return Err(Error::RepeatedParameter);
+        }
+
+        #[cfg(feature = "std")]
+        if metadata.finished_at.is_none() {
+            metadata.finished_at = Some(trial::now());
+        }
+        let trial = Trial {
+            id: id.unwrap_or(self.next_trial_id),
+            metric,
+            parameter,
+            metadata,
+        };
         let n_expected_good_trials = {
             // `+ 1` is for this new trial.
             let n_total_trials = self.good_trials.len() + self.bad_trials.len() + 1;
@@ -136,22 +327,31 @@ impl<KInit, P, M> Optimizer<KInit, P, M> {
             .is_some_and(|worst_good_trial| &trial <= worst_good_trial)
         {
             // New trial is not worse than the worst good trial, so it belongs to the good trials:
-            if self.good_trials.insert(trial) {
-                // Re-balance:
-                while self.good_trials.len() > n_expected_good_trials {
-                    self.bad_trials
-                        .insert(self.good_trials.pop_worst().unwrap());
-                }
+            if !self.good_trials.insert(trial) {
+                return Err(Error::RepeatedParameter);
+            }
+            // Re-balance:
+            while self.good_trials.len() > n_expected_good_trials {
+                self.bad_trials
+                    .insert(self.good_trials.pop_worst().unwrap());
             }
         }
         // Otherwise, it belongs to the bad trials:
-        else if self.bad_trials.insert(trial) {
+        else {
+            if !self.bad_trials.insert(trial) {
+                return Err(Error::RepeatedParameter);
+            }
             // Re-balance:
             while self.good_trials.len() < n_expected_good_trials {
                 self.good_trials.insert(self.bad_trials.pop_best().unwrap());
             }
         }
 
+        // The trial is inserted, so its ID is now taken:
+        if id.is_none() {
+            self.next_trial_id += 1;
+        }
+
         // Verify the invariant:
         #[cfg(debug_assertions)]
         {
@@ -164,167 +364,972 @@ impl<KInit, P, M> Optimizer<KInit, P, M> {
                 );
             }
         }
+
+        Ok(())
+    }
+
+    /// Check that the parameter has been neither tried, nor started yet.
+    fn is_untried(
+        parameter: &P,
+        good_trials: &Trials<P, M>,
+        bad_trials: &Trials<P, M>,
+        pending_trials: &BTreeMap<u64, P>,
+    ) -> bool
+    where
+        P: Copy + TotalOrd,
+    {
+        !good_trials.contains(parameter)
+            && !bad_trials.contains(parameter)
+            && !pending_trials
+                .values()
+                .any(|pending| pending.total_order(parameter).is_eq())
+    }
+
+    /// Take the next untried parameter from the initial design.
+    ///
+    /// The design gets dropped as soon as it is exhausted, or it yields too many tried parameters.
+    fn next_design_parameter<D>(&mut self) -> Result<Option<P>, Error>
+    where
+        P: Copy + TotalOrd,
+        D: FromPrimitive,
+        Uniform<P, D>: Quantile<Param = P, Probability = D>,
+    {
+        let Some(design) = self.initial_design.as_mut() else {
+            return Ok(None);
+        };
+        let uniform = Uniform::<P, D>::with_bounds(self.range.copy());
+        for point in design.take(self.max_rejections.saturating_add(1)) {
+            let probability = D::from_f64(point).ok_or(Error::ConversionFailed)?;
+            let parameter = Self::normalize(
+                uniform.quantile(probability),
+                &self.range,
+                self.quantization.as_deref(),
+            );
+            if self.allow_repeats
+                || Self::is_untried(
+                    &parameter,
+                    &self.good_trials,
+                    &self.bad_trials,
+                    &self.pending_trials,
+                )
+            {
+                return Ok(Some(parameter));
+            }
+        }
+        self.initial_design = None;
+        Ok(None)
+    }
+
+    /// Clamp the parameter to the search range, and snap it to the quantization grid, if any.
+    fn normalize(
+        parameter: P,
+        range: &RangeInclusive<P>,
+        quantization: Option<&(dyn Quantization<P> + Send)>,
+    ) -> P
+    where
+        P: Copy + TotalOrd,
+    {
+        let clamp = |parameter| num_traits::clamp(parameter, *range.start(), *range.end());
+        let parameter = clamp(parameter);
+
+        // Snapping may step out of the range, hence clamping once again:
+        quantization.map_or(parameter, |quantization| {
+            clamp(quantization.snap(parameter))
+        })
+    }
+
+    /// Wrap the parameter around into the periodic search range.
+    fn wrap(parameter: P, range: &RangeInclusive<P>) -> P
+    where
+        P: Copy + TotalOrd + Arithmetic + Rem + Zero,
+    {
+        let period = *range.end() - *range.start();
+        let offset = (parameter - *range.start()) % period;
+        if offset < P::zero() {
+            *range.start() + offset + period
+        } else {
+            *range.start() + offset
+        }
+    }
+
+    /// Calculate the log-mass within the quantization bucket, if any, or the log-density otherwise.
+    ///
+    /// The mass is exact for the kernels, which implement [`crate::Cdf`], see [`Density::log_mass`].
+    /// Since all the densities of a candidate are integrated over the same bucket, the masses
+    /// are comparable to each other in the acquisition function. A degenerate bucket,
+    /// for example, of an integer snapped with the unit step, falls back to the density.
+    ///
+    /// The bucket start is clamped to the search range, since the smallest logarithmic bucket
+    /// extends down to the negative infinity.
+    fn bucket_log_density<D>(
+        density: &D,
+        parameter: P,
+        range: &RangeInclusive<P>,
+        quantization: Option<&(dyn Quantization<P> + Send)>,
+    ) -> D::Output
+    where
+        P: Copy + TotalOrd + ToPrimitive + FromPrimitive,
+        D: Density<Param = P>,
+        D::Output: Copy + Arithmetic + PartialOrd + Exp + Ln + FromPrimitive + Zero + One,
+    {
+        match quantization.map(|quantization| quantization.bucket(parameter)) {
+            Some(Range { start, end }) if start < end => {
+                density.log_mass(start.total_max(*range.start())..end)
+            }
+            _ => density.log_density(parameter),
+        }
     }
 
     /// Construct the kernel for the triple of adjacent trials.
-    fn construct_kernel<K>(triple: Triple<P>, bounds: RangeInclusive<P>, bandwidth: P) -> K
+    ///
+    /// The kernel is bounded by the search range, unless it gets reflected across the bounds.
+    fn construct_kernel(
+        triple: Triple<P>,
+        bounds: RangeInclusive<P>,
+        scale: Scale,
+        bounded: bool,
+    ) -> Result<K, Error>
     where
         K: Kernel<Param = P>,
-        P: Copy + Ord + Arithmetic,
+        P: Copy + TotalOrd + Arithmetic + Zero + ToPrimitive + FromPrimitive,
     {
-        match triple {
+        let (location, std) = match triple {
             Triple::Full(left, location, right) => {
                 // For the middle point we take the maximum of the distances to the left and right neighbors:
-                Kernel::new(
-                    location,
-                    bandwidth * (right - location).max(location - left),
-                )
+                (location, (right - location).total_max(location - left))
             }
 
             Triple::LeftMiddle(left, location) => {
                 // For the left-middle pair: the maximum between them and to the right bound:
-                K::new(
+                (
                     location,
-                    bandwidth * (location - left).max(*bounds.end() - location),
+                    (location - left).total_max(*bounds.end() - location),
                 )
             }
 
             Triple::MiddleRight(location, right) => {
                 // Similar, but to the left bound:
-                K::new(
+                (
                     location,
-                    bandwidth * (right - location).max(location - *bounds.start()),
+                    (right - location).total_max(location - *bounds.start()),
                 )
             }
 
             Triple::Left(location) | Triple::Middle(location) | Triple::Right(location) => {
                 // Maximum between the distances to the bounds:
-                K::new(
+                (
                     location,
-                    bandwidth * (*bounds.end() - location).max(location - *bounds.start()),
+                    (*bounds.end() - location).total_max(location - *bounds.start()),
                 )
             }
-        }
+        };
+        let std = scale.apply(std.to_f64().ok_or(Error::ConversionFailed)?);
+        K::try_new_scaled(location, std, bounded.then_some(bounds))
     }
 
     /// Construct a [`KernelDensityEstimator`] from the trials.
-    fn construct_kde<K>(
+    ///
+    /// For the periodic search range, the first and last trials are each other's neighbours.
+    fn construct_kde(
         parameters: impl Iterator<Item = P> + Clone,
         bounds: RangeInclusive<P>,
-        bandwidth: P,
-    ) -> KernelDensityEstimator<impl Iterator<Item = K> + Clone>
+        scale: Scale,
+        reflection: bool,
+        periodic: bool,
+    ) -> Result<KernelDensityEstimator<vec::IntoIter<K>, P>, Error>
     where
-        P: Copy + Ord + Arithmetic,
+        P: Copy + TotalOrd + Arithmetic + Zero + ToPrimitive + FromPrimitive,
         K: Copy + Kernel<Param = P>,
     {
-        KernelDensityEstimator(
-            Triples::new(parameters)
-                .map(move |triple| Self::construct_kernel(triple, bounds.copy(), bandwidth)),
-        )
+        let reflection = reflection && !periodic;
+
+        // Wrap the neighbours around, so that each trial gets a full triple:
+        let (head, tail) = if periodic {
+            let period = *bounds.end() - *bounds.start();
+            (
+                parameters.clone().last().map(|last| last - period),
+                parameters.clone().next().map(|first| first + period),
+            )
+        } else {
+            (None, None)
+        };
+        let kernels = Triples::new(head.into_iter().chain(parameters).chain(tail))
+            .filter(|triple| !periodic || matches!(triple, Triple::Full(..)))
+            .map(|triple| Self::construct_kernel(triple, bounds.copy(), scale, !reflection))
+            .collect::<Result<Vec<K>, Error>>()?;
+
+        let kde = KernelDensityEstimator::new(kernels.into_iter());
+        Ok(if reflection {
+            kde.reflection(bounds)
+        } else {
+            kde
+        })
     }
 
     /// Generate a parameter value for a new trial.
     ///
     /// After evaluating the target function with this parameter,
     /// you'd better feed the metric back with [`Optimizer::feed_back`].
+    /// See also [`Optimizer::start_trial`], which keeps track of the pending trials.
     ///
-    /// # Type parameters
+    /// # Panics
     ///
-    /// - [`K`]: kernel type
-    /// - [`D`]: kernel density type
+    /// This method panics on failure, see [`Optimizer::try_new_trial`] for the non-panicking version.
+    pub fn new_trial(&mut self) -> P
+    where
+        KInit: Density<Param = P> + Sample<Param = P>,
+        KInit::Output: Copy + Debug + TotalOrd + Arithmetic + Exp + Ln + FromPrimitive + Zero + One,
+        K: Copy
+            + Kernel<Param = P>
+            + Sample<Param = P>
+            + Density<Param = P, Output = <KInit as Density>::Output>,
+        P: Copy + TotalOrd + Arithmetic + Rem + Zero + Countable + ToPrimitive + FromPrimitive,
+        Uniform<P, KInit::Output>: Quantile<Param = P, Probability = KInit::Output>,
+    {
+        self.try_new_trial()
+            .unwrap_or_else(|error| panic!("failed to generate a new trial: {error}"))
+    }
+
+    /// Generate a parameter value for a new trial, just like [`Optimizer::new_trial`],
+    /// but return an error instead of panicking.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// This method may panic if a random or calculated number cannot be converted to
-    /// the parameter or density type.
+    /// - [`Error::ConversionFailed`]: a random or calculated number cannot be converted to
+    ///   the parameter or density type
+    /// - [`Error::InvalidBandwidth`]: a trial kernel cannot be constructed, for example,
+    ///   because of a zero bandwidth
+    /// - [`Error::NonPositiveDensity`]: the «bad» density vanishes at a candidate, which means
+    ///   that the initial kernel does not cover the search range
+    /// - [`Error::Exhausted`]: all the samples were rejected as already tried or started,
+    ///   see [`OptimizerBuilder::max_rejections`]
     #[allow(clippy::cast_precision_loss)]
-    pub fn new_trial<K>(&mut self) -> P
+    pub fn try_new_trial(&mut self) -> Result<P, Error>
     where
-        KInit: Copy + Density<Param = P> + Sample<Param = P>,
-        KInit::Output: Copy + Debug + Ord + Arithmetic + FromPrimitive + Zero,
+        KInit: Density<Param = P> + Sample<Param = P>,
+        KInit::Output: Copy + Debug + TotalOrd + Arithmetic + Exp + Ln + FromPrimitive + Zero + One,
         K: Copy
             + Kernel<Param = P>
             + Sample<Param = P>
             + Density<Param = P, Output = <KInit as Density>::Output>,
-        P: Copy + Ord + Arithmetic,
+        P: Copy + TotalOrd + Arithmetic + Rem + Zero + Countable + ToPrimitive + FromPrimitive,
+        Uniform<P, KInit::Output>: Quantile<Param = P, Probability = KInit::Output>,
     {
+        // Follow the initial design first, if any:
+        if let Some(parameter) = self.next_design_parameter::<KInit::Output>()? {
+            return Ok(parameter);
+        }
+
         // Abandon hope, all ye who enter here!
         // Okay… Slow breath in… and out…
 
         // First, construct the KDEs:
-        let good_kde = Self::construct_kde::<K>(
+        let good_kde = Self::construct_kde(
             self.good_trials.iter_parameters(),
             self.range.copy(),
-            self.bandwidth,
-        );
-        let bad_kde = Self::construct_kde::<K>(
+            self.scale,
+            self.reflection,
+            self.periodic,
+        )?;
+        let bad_kde = Self::construct_kde(
             self.bad_trials.iter_parameters(),
             self.range.copy(),
-            self.bandwidth,
-        );
+            self.scale,
+            self.reflection,
+            self.periodic,
+        )?;
+
+        // Calculate the acquisition function in the log space, so that the densities do not underflow:
+        let n_good_trials =
+            K::Output::from_usize(self.good_trials.len()).ok_or(Error::ConversionFailed)?;
+        let n_bad_trials =
+            K::Output::from_usize(self.bad_trials.len()).ok_or(Error::ConversionFailed)?;
+        let acquisition = |parameter| {
+            let quantization = self.quantization.as_deref();
 
-        // Now, sample candidates:
-        let candidates = iter::from_fn(|| {
-            if self.good_trials.len() < 2 || self.rng.usize(0..=self.good_trials.len()) == 0 {
-                // Select from the first component, if the good KDE is empty or with probability `1 / (n + 1)`.
-                Some(self.init_kernel.sample(&mut self.rng))
+            // Use weighted average of the initial component and KDE:
+            let init_log_density =
+                Self::bucket_log_density(&self.init_kernel, parameter, &self.range, quantization);
+            let log_l = log_sum_exp([
+                init_log_density,
+                n_good_trials.ln()
+                    + Self::bucket_log_density(&good_kde, parameter, &self.range, quantization),
+            ]) - (n_good_trials + K::Output::one()).ln();
+            let log_g = log_sum_exp([
+                init_log_density,
+                n_bad_trials.ln()
+                    + Self::bucket_log_density(&bad_kde, parameter, &self.range, quantization),
+            ]) - (n_bad_trials + K::Output::one()).ln();
+            if log_g > K::Output::zero().ln() {
+                Ok((parameter, log_l - log_g))
             } else {
-                // Select normally from the good KDE:
-                Some(
-                    good_kde
-                        .sample(&mut self.rng)
-                        .expect("KDE should return a sample"),
+                Err(Error::NonPositiveDensity)
+            }
+        };
+
+        let is_new = |parameter: &P| {
+            self.allow_repeats
+                || Self::is_untried(
+                    parameter,
+                    &self.good_trials,
+                    &self.bad_trials,
+                    &self.pending_trials,
                 )
+        };
+
+        // For a small discrete range, evaluate every untried value, once the good KDE is in use.
+        // Before that, the candidates get sampled from the initial kernel to explore the range.
+        let exhaustive = self.good_trials.len() >= 2
+            && P::n_values(&self.range).is_some_and(|n_values| n_values <= self.exhaustive_limit);
+        let best_candidate = if exhaustive {
+            let quantization = self.quantization.as_deref();
+            let new_values = P::values(self.range.copy())
+                // Only the grid points, when quantized:
+                .filter(|value| Self::normalize(*value, &self.range, quantization) == *value)
+                .filter(|value| is_new(value));
+            Self::best_candidate(new_values.map(&acquisition))?
+        } else {
+            // Now, sample candidates:
+            let candidates = iter::from_fn(|| {
+                if self.good_trials.len() < 2 || self.rng.usize(0..=self.good_trials.len()) == 0 {
+                    // Select from the first component, if the good KDE is empty or with probability `1 / (n + 1)`.
+                    Some(self.init_kernel.sample(&mut self.rng))
+                } else {
+                    // Select normally from the good KDE:
+                    Some(good_kde.sample(&mut self.rng))
+                }
+            });
+
+            // Wrap them around the period, if any, clamp to the bounds, and snap to the grid:
+            let valid_candidates = candidates
+                .map(|parameter| {
+                    if self.periodic {
+                        Self::wrap(parameter, &self.range)
+                    } else {
+                        parameter
+                    }
+                })
+                .map(|parameter| {
+                    Self::normalize(parameter, &self.range, self.quantization.as_deref())
+                });
+
+            // Filter out tried and started ones, until too many get rejected:
+            let mut n_rejections = 0_usize;
+            let new_candidates = valid_candidates
+                .map(|parameter| is_new(&parameter).then_some(parameter))
+                .take_while(|parameter| {
+                    if parameter.is_none() {
+                        n_rejections += 1;
+                    }
+                    n_rejections <= self.max_rejections
+                })
+                .flatten();
+
+            Self::best_candidate(new_candidates.map(&acquisition).take(self.n_candidates))?
+        };
+
+        best_candidate.ok_or(Error::Exhausted)
+    }
+
+    /// Take the candidate with the highest acquisition function value.
+    ///
+    /// Unlike in [`TotalOrd`], NaN ranks the lowest here, so that a NaN acquisition is only taken,
+    /// when no candidate has a valid one.
+    fn best_candidate<D>(
+        evaluated_candidates: impl Iterator<Item = Result<(P, D), Error>>,
+    ) -> Result<Option<P>, Error>
+    where
+        D: TotalOrd,
+    {
+        let mut best_candidate: Option<(P, D)> = None;
+        for candidate in evaluated_candidates {
+            let (parameter, acquisition) = candidate?;
+            if best_candidate.as_ref().is_none_or(|(_, best_acquisition)| {
+                match (acquisition.is_nan(), best_acquisition.is_nan()) {
+                    (false, false) => acquisition.total_order(best_acquisition).is_ge(),
+                    (is_nan, best_is_nan) => !is_nan || best_is_nan,
+                }
+            }) {
+                best_candidate = Some((parameter, acquisition));
             }
-        });
-
-        // Clamp them to the bounds:
-        let valid_candidates = candidates
-            .map(|parameter| num_traits::clamp(parameter, *self.range.start(), *self.range.end()));
-
-        // Filter out tried ones:
-        let new_candidates = valid_candidates
-            .filter(|parameter| !self.good_trials.contains(parameter))
-            .filter(|parameter| !self.bad_trials.contains(parameter));
-
-        // Calculate the acquisition function:
-        let evaluated_candidates = new_candidates
-            .map(|parameter| {
-                // Use weighted average of the initial component and KDE:
-                let init_density = self.init_kernel.density(parameter);
-                let l = (init_density
-                    + good_kde.density(parameter)
-                        * K::Output::from_usize(self.good_trials.len()).unwrap())
-                    / K::Output::from_usize(self.good_trials.len() + 1).unwrap();
-                assert!(
-                    l >= K::Output::zero(),
-                    "«good» density should not be negative: {l:?}"
-                );
-                let g = (init_density
-                    + bad_kde.density(parameter)
-                        * K::Output::from_usize(self.bad_trials.len()).unwrap())
-                    / K::Output::from_usize(self.bad_trials.len() + 1).unwrap();
-                assert!(
-                    g > K::Output::zero(),
-                    "«bad» density should be positive: {g:?}"
-                );
-                (parameter, l / g)
-            })
-            .take(self.n_candidates);
+        }
+        Ok(best_candidate.map(|(parameter, _)| parameter))
+    }
 
-        // Take the best one by the acquisition function value:
-        evaluated_candidates
-            .max_by_key(|(_, acquisition)| *acquisition)
-            .expect("there should be at least one candidate")
-            .0
+    /// Generate a parameter value for a new trial, just like [`Optimizer::new_trial`],
+    /// and register the trial as pending.
+    ///
+    /// Finish the trial with [`Optimizer::finish_trial`], which consumes the handle,
+    /// so that the same result cannot be fed back twice. With the `std` feature,
+    /// the handle has its [start timestamp][TrialMetadata::started_at] set to the current time.
+    ///
+    /// Unless [repeats are allowed][OptimizerBuilder::allow_repeats], the pending parameters
+    /// are not suggested again, so that concurrent workers get distinct parameters.
+    ///
+    /// # Panics
+    ///
+    /// This method panics on failure, see [`Optimizer::try_start_trial`] for the non-panicking version.
+    pub fn start_trial(&mut self) -> TrialHandle<P>
+    where
+        KInit: Density<Param = P> + Sample<Param = P>,
+        KInit::Output: Copy + Debug + TotalOrd + Arithmetic + Exp + Ln + FromPrimitive + Zero + One,
+        K: Copy
+            + Kernel<Param = P>
+            + Sample<Param = P>
+            + Density<Param = P, Output = <KInit as Density>::Output>,
+        P: Copy + TotalOrd + Arithmetic + Rem + Zero + Countable + ToPrimitive + FromPrimitive,
+        Uniform<P, KInit::Output>: Quantile<Param = P, Probability = KInit::Output>,
+    {
+        self.try_start_trial()
+            .unwrap_or_else(|error| panic!("failed to start a new trial: {error}"))
+    }
+
+    /// Start a new trial, just like [`Optimizer::start_trial`], but return an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// See [`Optimizer::try_new_trial`].
+    pub fn try_start_trial(&mut self) -> Result<TrialHandle<P>, Error>
+    where
+        KInit: Density<Param = P> + Sample<Param = P>,
+        KInit::Output: Copy + Debug + TotalOrd + Arithmetic + Exp + Ln + FromPrimitive + Zero + One,
+        K: Copy
+            + Kernel<Param = P>
+            + Sample<Param = P>
+            + Density<Param = P, Output = <KInit as Density>::Output>,
+        P: Copy + TotalOrd + Arithmetic + Rem + Zero + Countable + ToPrimitive + FromPrimitive,
+        Uniform<P, KInit::Output>: Quantile<Param = P, Probability = KInit::Output>,
+    {
+        let parameter = self.try_new_trial()?;
+        let id = self.next_trial_id();
+        self.pending_trials.insert(id, parameter);
+        let metadata = TrialMetadata::default();
+        #[cfg(feature = "std")]
+        let metadata = metadata.started_now();
+        Ok(TrialHandle {
+            id,
+            parameter,
+            metadata,
+        })
     }
 
     /// Get the best trial.
     pub fn best_trial(&self) -> Option<&Trial<P, M>>
     where
-        P: Ord,
-        M: Ord,
+        P: TotalOrd,
+        M: TotalOrd,
     {
         self.good_trials.best().or_else(|| self.bad_trials.best())
     }
+
+    /// Iterate the trials in the history from the best to the worst.
+    ///
+    /// Repeated parameters, ignored by [`Optimizer::feed_back`], are not included.
+    pub fn trials(&self) -> impl Iterator<Item = &Trial<P, M>> {
+        self.good_trials.iter().chain(self.bad_trials.iter())
+    }
+
+    /// Find the finished trial by its [ID][Trial::id].
+    pub fn trial(&self, id: u64) -> Option<&Trial<P, M>> {
+        self.trials().find(|trial| trial.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::time::Duration;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::{
+        design::Sobol,
+        kernel::{
+            continuous::Gaussian,
+            discrete::{Binomial, DiscreteGaussian},
+        },
+        quantization::Linear,
+    };
+
+    #[test]
+    fn best_candidate_skips_nan_ok() {
+        let best_candidate = |acquisitions: [f64; 3]| {
+            Optimizer::<Uniform<f64, f64>, f64, f64>::best_candidate(
+                [1.0, 2.0, 3.0].into_iter().zip(acquisitions).map(Ok),
+            )
+            .unwrap()
+        };
+        assert_eq!(best_candidate([f64::NAN, -1.0, f64::NAN]), Some(2.0));
+        assert_eq!(best_candidate([-1.0, f64::NAN, -2.0]), Some(1.0));
+        assert_eq!(best_candidate([f64::NAN; 3]), Some(3.0));
+    }
+
+    #[test]
+    fn nan_parameter_rejected_ok() {
+        let mut optimizer = Optimizer::builder(
+            0.0..=1.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+            Rng::with_seed(42),
+        )
+        .build();
+        assert_eq!(
+            optimizer.try_feed_back(f64::NAN, 1.0),
+            Err(Error::NanParameter)
+        );
+        assert!(optimizer.best_trial().is_none());
+    }
+
+    #[test]
+    fn repeated_parameter_ok() {
+        let mut optimizer = Optimizer::builder(
+            0.0..=1.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+            Rng::with_seed(42),
+        )
+        .build();
+        assert_eq!(optimizer.try_feed_back(0.5, 1.0), Ok(()));
+        assert_eq!(
+            optimizer.try_feed_back(0.5, 0.0),
+            Err(Error::RepeatedParameter)
+        );
+
+        // The panicking version ignores the repeat:
+        optimizer.feed_back(0.5, 0.0);
+        assert_eq!(optimizer.trials().count(), 1);
+
+        // Unless repeats are allowed:
+        let mut optimizer = Optimizer::builder(
+            0.0..=1.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+            Rng::with_seed(42),
+        )
+        .allow_repeats(true)
+        .build();
+        optimizer.feed_back(0.5, 1.0);
+        assert_eq!(optimizer.try_feed_back(0.5, 2.0), Ok(()));
+        assert_eq!(optimizer.trials().count(), 2);
+    }
+
+    #[test]
+    fn dense_ids_ok() {
+        let mut optimizer = Optimizer::builder(
+            0.0..=1.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+            Rng::with_seed(42),
+        )
+        .build();
+        optimizer.feed_back(0.1, 1.0);
+        optimizer.feed_back(0.1, 2.0);
+        assert_eq!(
+            optimizer.try_feed_back(f64::NAN, 3.0),
+            Err(Error::NanParameter)
+        );
+        optimizer.feed_back(0.2, 3.0);
+
+        // The ignored trials do not take the IDs:
+        let ids: Vec<_> = optimizer.trials().map(|trial| trial.id).collect();
+        assert_eq!(ids, [0, 1]);
+    }
+
+    #[test]
+    fn nan_metric_is_worst_ok() {
+        let mut optimizer = Optimizer::builder(
+            0.0..=1.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+            Rng::with_seed(42),
+        )
+        .kernel::<Gaussian<f64>>()
+        .build();
+        optimizer.feed_back(0.1, f64::NAN);
+        optimizer.feed_back(0.2, 1.0);
+        optimizer.feed_back(0.3, f64::INFINITY);
+        let best_trial = optimizer.best_trial().unwrap();
+        assert_abs_diff_eq!(best_trial.parameter, 0.2);
+
+        // And it is still able to suggest new trials:
+        let parameter = optimizer.new_trial();
+        assert!((0.0..=1.0).contains(&parameter));
+    }
+
+    #[test]
+    fn trial_metadata_ok() {
+        let mut optimizer = Optimizer::builder(
+            0.0..=1.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+            Rng::with_seed(42),
+        )
+        .build();
+        optimizer.feed_back(0.1, 2.0);
+        optimizer.feed_back_with_metadata(
+            0.2,
+            1.0,
+            TrialMetadata::default()
+                .started_at(Duration::from_secs(1))
+                .finished_at(Duration::from_secs(3))
+                .attribute("seed", "42"),
+        );
+
+        let ids: Vec<_> = optimizer.trials().map(|trial| trial.id).collect();
+        assert_eq!(ids, [1, 0]);
+
+        let trial = optimizer.trial(1).unwrap();
+        assert_abs_diff_eq!(trial.parameter, 0.2);
+        assert_eq!(trial.duration(), Some(Duration::from_secs(2)));
+        assert_eq!(trial.metadata.attributes["seed"], "42");
+
+        #[cfg(feature = "std")]
+        assert!(optimizer.trial(0).unwrap().metadata.finished_at.is_some());
+        assert!(optimizer.trial(2).is_none());
+    }
+
+    #[test]
+    fn trial_handle_ok() {
+        let mut optimizer = Optimizer::builder(
+            0.0..=1.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+            Rng::with_seed(42),
+        )
+        .kernel::<Gaussian<f64>>()
+        .build();
+        optimizer.feed_back(0.5, 1.0);
+        let first = optimizer.start_trial();
+        let mut second = optimizer.start_trial();
+        second.metadata = second.metadata.attribute("seed", "42");
+        assert_eq!((first.id(), second.id()), (1, 2));
+
+        let pending: Vec<_> = optimizer.pending_trials().map(|(id, _)| id).collect();
+        assert_eq!(pending, [1, 2]);
+
+        let second_parameter = second.parameter();
+        optimizer.finish_trial(second, 0.0).unwrap();
+        let pending: Vec<_> = optimizer.pending_trials().map(|(id, _)| id).collect();
+        assert_eq!(pending, [1]);
+
+        let trial = optimizer.trial(2).unwrap();
+        assert_abs_diff_eq!(trial.parameter, second_parameter);
+        assert_eq!(trial.metadata.attributes["seed"], "42");
+        #[cfg(feature = "std")]
+        assert!(trial.duration().is_some());
+
+        // The handle from another optimizer is rejected:
+        let mut other = Optimizer::builder(
+            0.0..=1.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+            Rng::with_seed(42),
+        )
+        .build();
+        assert_eq!(
+            other.finish_trial(first, 0.0),
+            Err(Error::UnknownTrial { id: 1 })
+        );
+    }
+
+    #[test]
+    fn pending_trials_not_repeated_ok() {
+        let mut optimizer = Optimizer::builder(
+            0..=1,
+            Uniform::<i32, f64>::with_bounds(0..=1),
+            Rng::with_seed(42),
+        )
+        .kernel::<Binomial<i32, f64>>()
+        .build();
+        let first = optimizer.start_trial();
+        let second = optimizer.start_trial();
+        assert_ne!(first.parameter(), second.parameter());
+        assert_eq!(optimizer.try_start_trial().unwrap_err(), Error::Exhausted);
+
+        // Feeding back the pending parameter makes its handle's result ignored:
+        optimizer.feed_back(first.parameter(), 1);
+        assert_eq!(
+            optimizer.finish_trial(first, 1),
+            Err(Error::RepeatedParameter)
+        );
+        assert_eq!(optimizer.finish_trial(second, 0), Ok(()));
+    }
+
+    #[test]
+    fn quantization_type_bounds_ok() {
+        // The grid point `256` and the bucket edges past it do not fit into `u8`:
+        let mut optimizer = Optimizer::builder(
+            0..=255,
+            Uniform::<u8, f64>::with_bounds(0..=255),
+            Rng::with_seed(42),
+        )
+        .kernel::<DiscreteGaussian<u8, f64>>()
+        .quantization(Linear::new(32))
+        .allow_repeats(true)
+        .build();
+        for _ in 0..50 {
+            let parameter = optimizer.try_new_trial().unwrap();
+            assert!(parameter % 32 == 0 || parameter == u8::MAX, "{parameter}");
+            optimizer.feed_back(parameter, u8::MAX - parameter);
+        }
+        assert_eq!(optimizer.best_trial().unwrap().parameter, u8::MAX);
+    }
+
+    #[test]
+    fn abandon_trial_ok() {
+        let mut optimizer: Optimizer<_, _, i32, _> = Optimizer::builder(
+            0..=1,
+            Uniform::<i32, f64>::with_bounds(0..=1),
+            Rng::with_seed(42),
+        )
+        .kernel::<Binomial<i32, f64>>()
+        .build();
+        let first = optimizer.start_trial();
+        let second = optimizer.start_trial();
+        assert_eq!(optimizer.try_start_trial().unwrap_err(), Error::Exhausted);
+
+        // Abandoning the trials makes their parameters eligible again:
+        let (first_id, first_parameter) = (first.id(), first.parameter());
+        optimizer.abandon_trial(first).unwrap();
+        assert_eq!(optimizer.start_trial().parameter(), first_parameter);
+        assert_eq!(
+            optimizer.abandon_trial_by_id(second.id()),
+            Ok(second.parameter())
+        );
+        assert_eq!(optimizer.start_trial().parameter(), second.parameter());
+        assert_eq!(
+            optimizer.abandon_trial_by_id(first_id),
+            Err(Error::UnknownTrial { id: first_id })
+        );
+    }
+
+    #[test]
+    fn try_new_trial_ok() {
+        let mut optimizer = Optimizer::builder(
+            0.0..=1.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+            Rng::with_seed(42),
+        )
+        .kernel::<Gaussian<f64>>()
+        .build();
+        for parameter in [0.1, 0.2, 0.3] {
+            optimizer.feed_back(parameter, parameter);
+        }
+        let parameter = optimizer.try_new_trial().unwrap();
+        assert!((0.0..=1.0).contains(&parameter));
+    }
+
+    #[test]
+    fn exhausted_ok() {
+        let mut optimizer = Optimizer::builder(
+            0..=9,
+            Uniform::<i32, f64>::with_bounds(0..=9),
+            Rng::with_seed(42),
+        )
+        .kernel::<Binomial<i32, f64>>()
+        .build();
+        let mut parameters = Vec::new();
+        for _ in 0..10 {
+            let parameter = optimizer.new_trial();
+            optimizer.feed_back(parameter, parameter);
+            parameters.push(parameter);
+        }
+        parameters.sort_unstable();
+        assert_eq!(parameters, (0..=9).collect::<Vec<_>>());
+        assert_eq!(optimizer.try_new_trial(), Err(Error::Exhausted));
+    }
+
+    #[test]
+    fn exhaustive_ok() {
+        let new_optimizer = |seed, exhaustive_limit: usize| {
+            let mut optimizer = Optimizer::builder(
+                0..=99,
+                Uniform::<i32, f64>::with_bounds(0..=99),
+                Rng::with_seed(seed),
+            )
+            .kernel::<Binomial<i32, f64>>()
+            .cutoff(0.25)
+            .exhaustive_limit(exhaustive_limit)
+            .build();
+            for parameter in [10_i32, 20, 30, 40, 50, 60, 70, 80, 90] {
+                optimizer.feed_back(parameter, (parameter - 42).abs());
+            }
+            optimizer
+        };
+
+        // The acquisition function gets maximized exactly, so the random seed does not matter:
+        let parameter = new_optimizer(1, 256).new_trial();
+        assert_eq!(new_optimizer(2, 256).new_trial(), parameter);
+
+        // Unless it gets sampled:
+        let parameters: Vec<_> = (1..=10)
+            .map(|seed| new_optimizer(seed, 0).new_trial())
+            .collect();
+        assert!(parameters.iter().any(|other| *other != parameters[0]));
+    }
+
+    #[test]
+    fn send_ok() {
+        fn assert_send<T: Send>(_: &T) {}
+
+        let optimizer = Optimizer::<_, _, f64>::builder(
+            0.0..=1.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+            Rng::with_seed(42),
+        )
+        .kernel::<Gaussian<f64>>()
+        .initial_design(Sobol::default().take(10))
+        .build();
+        assert_send(&optimizer);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_new_ok() {
+        let mut optimizer: Optimizer<_, _, f64, Gaussian<f64>> = Optimizer::new(
+            0.0..=1.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+            Rng::with_seed(42),
+        );
+        let parameter = optimizer.new_trial();
+        optimizer.feed_back(parameter, parameter);
+        assert!(optimizer.best_trial().is_some());
+    }
+
+    #[test]
+    fn try_build_ok() {
+        let builder = |range| {
+            Optimizer::<_, _, f64>::builder(
+                range,
+                Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+                Rng::with_seed(42),
+            )
+        };
+        assert!(builder(0.0..=1.0).try_build().is_ok());
+        assert!(builder(0.0..=1.0).hyperopt_preset().try_build().is_ok());
+        assert!(builder(0.0..=1.0).optuna_preset().try_build().is_ok());
+        assert!(Optimizer::<_, _, f64>::builder(
+            0..=0,
+            Uniform::<i32, f64>::with_bounds(0..=0),
+            Rng::with_seed(42),
+        )
+        .try_build()
+        .is_ok());
+
+        let error = |builder: OptimizerBuilder<_, _, _>| builder.try_build().unwrap_err();
+        assert_eq!(error(builder(0.0..=0.0)), Error::InvalidRange);
+        assert_eq!(error(builder(1.0..=0.0)), Error::InvalidRange);
+        assert_eq!(error(builder(f64::NAN..=1.0)), Error::InvalidRange);
+        assert_eq!(
+            error(builder(0.0..=0.0).periodic(true)),
+            Error::InvalidRange
+        );
+        assert_eq!(error(builder(0.0..=1.0).cutoff(0.0)), Error::InvalidCutoff);
+        assert_eq!(
+            error(builder(0.0..=1.0).cutoff(f64::NAN)),
+            Error::InvalidCutoff
+        );
+        assert_eq!(
+            error(builder(0.0..=1.0).n_candidates(0_usize)),
+            Error::NoCandidates
+        );
+        assert_eq!(
+            error(builder(0.0..=1.0).bandwidth(0.0)),
+            Error::InvalidBandwidth
+        );
+        assert_eq!(
+            error(builder(0.0..=1.0).bandwidth(-1.0)),
+            Error::InvalidBandwidth
+        );
+    }
+
+    #[test]
+    fn min_bandwidth_ok() {
+        let new_optimizer = |min_bandwidth: Option<BandwidthBound<i32>>| {
+            let mut builder = Optimizer::builder(
+                0..=0,
+                Uniform::<i32, f64>::with_bounds(-1..=1),
+                Rng::with_seed(42),
+            )
+            .kernel::<Binomial<i32, f64>>()
+            .allow_repeats(true);
+            if let Some(min_bandwidth) = min_bandwidth {
+                builder = builder.min_bandwidth(min_bandwidth);
+            }
+            let mut optimizer = builder.build();
+            optimizer.feed_back(0, 1.0);
+            optimizer.feed_back(0, 2.0);
+            optimizer
+        };
+        assert_eq!(
+            new_optimizer(None).try_new_trial(),
+            Err(Error::InvalidBandwidth)
+        );
+        assert_eq!(
+            new_optimizer(Some(BandwidthBound::Absolute(1))).try_new_trial(),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    fn bandwidth_bounds_validated_ok() {
+        let result = Optimizer::<_, _, f64>::builder(
+            0.0..=10.0,
+            Uniform::<f64, f64>::with_bounds(0.0..=10.0),
+            Rng::with_seed(42),
+        )
+        .min_bandwidth(BandwidthBound::Relative(0.1))
+        .max_bandwidth(BandwidthBound::Absolute(0.5))
+        .try_build();
+        assert_eq!(result.unwrap_err(), Error::InvalidBandwidth);
+    }
+
+    #[test]
+    fn fractional_bandwidth_ok() {
+        let mut optimizer = Optimizer::builder(
+            0..=1000,
+            Uniform::<i32, f64>::with_bounds(0..=1000),
+            Rng::with_seed(42),
+        )
+        .kernel::<Binomial<i32, f64>>()
+        .bandwidth(0.5)
+        .exhaustive_limit(0_usize)
+        .build();
+        for _ in 0..50 {
+            let parameter = optimizer.new_trial();
+            optimizer.feed_back(parameter, (parameter - 42).abs());
+        }
+        let best_trial = optimizer.best_trial().unwrap();
+        assert!((best_trial.parameter - 42).abs() <= 10, "{best_trial:?}");
+    }
+
+    #[test]
+    fn wide_discrete_range_ok() {
+        for end in [30_000, 50_000] {
+            let mut optimizer = Optimizer::builder(
+                0..=end,
+                Uniform::<i32, f64>::with_bounds(0..=end),
+                Rng::with_seed(42),
+            )
+            .kernel::<Binomial<i32, f64>>()
+            .build();
+            for _ in 0..20 {
+                let parameter = optimizer.new_trial();
+                assert!((0..=end).contains(&parameter));
+                optimizer.feed_back(parameter, (parameter - 42).abs());
+            }
+        }
+    }
+
+    #[test]
+    fn fractional_bandwidth_discrete_kernel_ok() {
+        let mut optimizer = Optimizer::builder(
+            0..=1000,
+            Uniform::<i32, f64>::with_bounds(0..=1000),
+            Rng::with_seed(42),
+        )
+        .kernel::<DiscreteGaussian<i32, f64>>()
+        .bandwidth(0.9)
+        .exhaustive_limit(0_usize)
+        .build();
+        for parameter in 100..105 {
+            optimizer.feed_back(parameter, parameter);
+        }
+
+        // The unit neighbour distances, scaled down below one, must not get truncated to zero:
+        assert!(optimizer.try_new_trial().is_ok());
+    }
 }
@@ -0,0 +1,105 @@
+use core::ops::RangeInclusive;
+
+use num_traits::ToPrimitive;
+
+use crate::{traits::ops::Additive, Error};
+
+/// Lower or upper bound of the kernel bandwidth,
+/// see [`crate::OptimizerBuilder::min_bandwidth`] and [`crate::OptimizerBuilder::max_bandwidth`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BandwidthBound<P> {
+    /// Bound in the parameter units.
+    Absolute(P),
+
+    /// Bound as a fraction of the search range width.
+    Relative(f64),
+}
+
+impl<P> BandwidthBound<P> {
+    /// Calculate the bound in the parameter units, as a real number.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::InvalidBandwidth`]: the bound is not positive
+    /// - [`Error::ConversionFailed`]: the bound or the range width cannot be converted to [`f64`]
+    pub fn resolve(self, range: &RangeInclusive<P>) -> Result<f64, Error>
+    where
+        P: Copy + Additive + ToPrimitive,
+    {
+        let bound = match self {
+            Self::Absolute(bound) => bound.to_f64().ok_or(Error::ConversionFailed)?,
+            Self::Relative(fraction) => {
+                let width = (*range.end() - *range.start())
+                    .to_f64()
+                    .ok_or(Error::ConversionFailed)?;
+                width * fraction
+            }
+        };
+        if bound.is_finite() && bound > 0.0 {
+            Ok(bound)
+        } else {
+            Err(Error::InvalidBandwidth)
+        }
+    }
+}
+
+/// Bandwidth settings, resolved for the kernel construction.
+#[derive(Copy, Clone, Debug)]
+pub struct Scale {
+    pub multiplier: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl Scale {
+    /// Multiply the standard deviation, and clamp it to the bounds.
+    ///
+    /// The lower bound wins over the upper one, so that the kernel never degenerates.
+    pub fn apply(&self, std: f64) -> f64 {
+        let std = self.multiplier * std;
+        let std = self.max.map_or(std, |max| std.min(max));
+        self.min.map_or(std, |min| std.max(min))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn resolve_ok() {
+        assert_abs_diff_eq!(
+            BandwidthBound::Relative(0.1).resolve(&(1.0..=3.0)).unwrap(),
+            0.2
+        );
+        assert_abs_diff_eq!(
+            BandwidthBound::<i32>::Relative(0.05)
+                .resolve(&(0..=9))
+                .unwrap(),
+            0.45
+        );
+        assert_abs_diff_eq!(BandwidthBound::Absolute(2).resolve(&(0..=9)).unwrap(), 2.0);
+        assert_eq!(
+            BandwidthBound::Absolute(0).resolve(&(0..=9)),
+            Err(Error::InvalidBandwidth)
+        );
+        assert_eq!(
+            BandwidthBound::<f64>::Relative(f64::NAN).resolve(&(0.0..=1.0)),
+            Err(Error::InvalidBandwidth)
+        );
+    }
+
+    #[test]
+    fn apply_ok() {
+        let scale = Scale {
+            multiplier: 2.0,
+            min: Some(0.5),
+            max: Some(4.0),
+        };
+        assert_abs_diff_eq!(scale.apply(0.0), 0.5);
+        assert_abs_diff_eq!(scale.apply(1.0), 2.0);
+        assert_abs_diff_eq!(scale.apply(10.0), 4.0);
+    }
+}
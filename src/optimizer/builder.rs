@@ -0,0 +1,402 @@
+use alloc::{boxed::Box, collections::BTreeMap};
+use core::{marker::PhantomData, ops::RangeInclusive};
+
+use fastrand::Rng;
+use num_traits::ToPrimitive;
+
+use crate::{
+    design::Design,
+    optimizer::{bandwidth::Scale, trial::Trials, BandwidthBound, Optimizer},
+    quantization::Quantization,
+    traits::ops::{Additive, Countable},
+    Error,
+};
+
+/// Builder of the [`Optimizer`], which validates the configuration once, when building it.
+///
+/// It is constructed with [`Optimizer::builder`].
+#[derive(Debug)]
+pub struct OptimizerBuilder<KInit, P, M, K = ()> {
+    range: RangeInclusive<P>,
+    init_kernel: KInit,
+    rng: Rng,
+    initial_design: Option<Box<dyn Design + Send>>,
+    quantization: Option<Box<dyn Quantization<P> + Send>>,
+    cutoff: f64,
+    n_candidates: usize,
+    max_rejections: usize,
+    exhaustive_limit: usize,
+    bandwidth: f64,
+    min_bandwidth: Option<BandwidthBound<P>>,
+    max_bandwidth: Option<BandwidthBound<P>>,
+    allow_repeats: bool,
+    reflection: bool,
+    periodic: bool,
+    optimizer: PhantomData<fn() -> (M, K)>,
+}
+
+impl<KInit, P, M> OptimizerBuilder<KInit, P, M> {
+    pub(super) const fn new(range: RangeInclusive<P>, init_kernel: KInit, rng: Rng) -> Self {
+        Self {
+            range,
+            init_kernel,
+            rng,
+            initial_design: None,
+            quantization: None,
+            cutoff: 0.1,
+            n_candidates: 25,
+            max_rejections: 1_000,
+            exhaustive_limit: 256,
+            bandwidth: 1.0,
+            min_bandwidth: None,
+            max_bandwidth: None,
+            allow_repeats: false,
+            reflection: false,
+            periodic: false,
+            optimizer: PhantomData,
+        }
+    }
+
+    /// Set the kernel type of the trial components.
+    ///
+    /// The kernel is a part of the optimizer type, so that all the trials of the study
+    /// use the same kernel, and [`Optimizer::new_trial`] does not need a type argument.
+    /// The kernel can only be chosen once.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fastrand::Rng;
+    /// use hyperopt::{
+    ///     kernel::{continuous::Gaussian, universal::Uniform},
+    ///     Optimizer,
+    /// };
+    ///
+    /// let mut optimizer = Optimizer::builder(
+    ///     0.0..=1.0,
+    ///     Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+    ///     Rng::with_seed(42),
+    /// )
+    /// .kernel::<Gaussian<f64>>()
+    /// .build();
+    /// let parameter = optimizer.new_trial();
+    /// optimizer.feed_back(parameter, parameter);
+    /// ```
+    #[must_use]
+    pub fn kernel<K>(self) -> OptimizerBuilder<KInit, P, M, K> {
+        OptimizerBuilder {
+            range: self.range,
+            init_kernel: self.init_kernel,
+            rng: self.rng,
+            initial_design: self.initial_design,
+            quantization: self.quantization,
+            cutoff: self.cutoff,
+            n_candidates: self.n_candidates,
+            max_rejections: self.max_rejections,
+            exhaustive_limit: self.exhaustive_limit,
+            bandwidth: self.bandwidth,
+            min_bandwidth: self.min_bandwidth,
+            max_bandwidth: self.max_bandwidth,
+            allow_repeats: self.allow_repeats,
+            reflection: self.reflection,
+            periodic: self.periodic,
+            optimizer: PhantomData,
+        }
+    }
+}
+
+impl<KInit, P, M, K> OptimizerBuilder<KInit, P, M, K> {
+    /// Apply the defaults of the original [`hyperopt`][1]: 25% of «good» trials,
+    /// and 24 candidates.
+    ///
+    /// [1]: https://github.com/hyperopt/hyperopt
+    #[must_use]
+    pub const fn hyperopt_preset(mut self) -> Self {
+        self.cutoff = 0.25;
+        self.n_candidates = 24;
+        self
+    }
+
+    /// Apply the defaults of the [Optuna][1] TPE sampler: 10% of «good» trials,
+    /// and 24 candidates.
+    ///
+    /// [1]: https://optuna.org
+    #[must_use]
+    pub const fn optuna_preset(mut self) -> Self {
+        self.cutoff = 0.1;
+        self.n_candidates = 24;
+        self
+    }
+
+    /// Set the ratio of «good» trials.
+    ///
+    /// It should be within `(0, 1]`, see [`OptimizerBuilder::try_build`].
+    #[must_use]
+    pub fn cutoff(mut self, cutoff: impl Into<f64>) -> Self {
+        self.cutoff = cutoff.into();
+        self
+    }
+
+    /// Set the number of candidates to choose the next trial from the acquisition function[^1].
+    ///
+    /// Sampling from the acquisition function is cheaper than evaluating the target cost function,
+    /// so the more candidates – the more effective is the optimization step.
+    ///
+    /// However, the acquisition function is an approximation of a potential gain,
+    /// so the fewer candidates – the more precise is the optimization step.
+    ///
+    /// The number of candidates is therefore a tradeoff.
+    ///
+    /// [^1]: Acquisition function is basically a ratio
+    ///       between the «good» KDE and «bad» KDE at the same point.
+    #[must_use]
+    pub fn n_candidates(mut self, n_candidates: impl Into<usize>) -> Self {
+        self.n_candidates = n_candidates.into();
+        self
+    }
+
+    /// Set the maximum number of the already tried samples, which [`Optimizer::new_trial`]
+    /// rejects before giving up.
+    ///
+    /// Unless [repeats are allowed][OptimizerBuilder::allow_repeats], a small discrete search space
+    /// eventually gets exhausted, and then all the samples get rejected. When the limit is reached,
+    /// the optimizer picks the best of the candidates found so far, or, if there are none,
+    /// considers the search space exhausted.
+    #[must_use]
+    pub fn max_rejections(mut self, max_rejections: impl Into<usize>) -> Self {
+        self.max_rejections = max_rejections.into();
+        self
+    }
+
+    /// Set the maximum size of a discrete search range, on which the acquisition function
+    /// gets evaluated exhaustively.
+    ///
+    /// For such a small range, [`Optimizer::new_trial`] evaluates every untried value
+    /// instead of sampling [the candidates][OptimizerBuilder::n_candidates], so that the true maximum
+    /// of the acquisition function is picked. Only [countable][crate::ops::Countable] parameter types
+    /// are enumerated, and zero disables the exhaustive evaluation.
+    #[must_use]
+    pub fn exhaustive_limit(mut self, exhaustive_limit: impl Into<usize>) -> Self {
+        self.exhaustive_limit = exhaustive_limit.into();
+        self
+    }
+
+    /// Set the bandwidth multiplier for the estimator kernels.
+    ///
+    /// Standard deviation of the kernel is the distance from the point to its furthest neighbour,
+    /// multiplied by this coefficient.
+    ///
+    /// The default multiplier is `1`. Lower bandwidth approximates the density better,
+    /// however, is also prone to over-fitting. Higher bandwidth avoid over-fitting better,
+    /// but is also smoother and less precise.
+    ///
+    /// The multiplier is real-valued regardless of the parameter type, so that integer parameters
+    /// may use fractional multipliers, too.
+    #[must_use]
+    pub fn bandwidth(mut self, bandwidth: impl Into<f64>) -> Self {
+        self.bandwidth = bandwidth.into();
+        self
+    }
+
+    /// Set the lower bound of the kernel bandwidth, which is applied after the multiplier.
+    ///
+    /// It prevents the degenerate kernels, for example, when the adjacent trials coincide.
+    /// The lower bound wins over [the upper one][OptimizerBuilder::max_bandwidth].
+    #[must_use]
+    pub fn min_bandwidth(mut self, min_bandwidth: BandwidthBound<P>) -> Self {
+        self.min_bandwidth = Some(min_bandwidth);
+        self
+    }
+
+    /// Set the upper bound of the kernel bandwidth, which is applied after the multiplier.
+    ///
+    /// It prevents the kernels of the sparse trials from spanning the whole search range.
+    #[must_use]
+    pub fn max_bandwidth(mut self, max_bandwidth: BandwidthBound<P>) -> Self {
+        self.max_bandwidth = Some(max_bandwidth);
+        self
+    }
+
+    /// Allow trying and feeding back the same parameter more than once.
+    ///
+    /// By default, [`Optimizer::new_trial`] never suggests a tried parameter,
+    /// and [`Optimizer::feed_back`] ignores repeated parameters. However, when the optimizer tunes
+    /// a single parameter of a multi-parameter search space, the same value legitimately occurs
+    /// in different trials.
+    #[must_use]
+    pub const fn allow_repeats(mut self, allow_repeats: bool) -> Self {
+        self.allow_repeats = allow_repeats;
+        self
+    }
+
+    /// Reflect the estimators' components across the search range bounds.
+    ///
+    /// By default, the sampled candidates are clamped to the search range, and the components
+    /// get the range as their bounds, so that a [`crate::kernel::truncated::Truncated`] kernel
+    /// truncates itself to it. With the reflection, the components get no bounds,
+    /// and [`crate::kde::KernelDensityEstimator::reflection`] corrects the estimators' densities
+    /// near the bounds instead. This works better for continuous parameters,
+    /// whose optimum is on a bound.
+    #[must_use]
+    pub const fn reflection(mut self, reflection: bool) -> Self {
+        self.reflection = reflection;
+        self
+    }
+
+    /// Treat the search range as periodic, with the bounds being the same point.
+    ///
+    /// This is meant for parameters, which wrap around, such as angles, phases or time of day:
+    /// for example, `0.0..=360.0` for degrees. The neighbour distances are measured
+    /// around the period, and the candidates are wrapped into the range instead of being clamped.
+    /// The reflection gets ignored.
+    ///
+    /// Kernels should wrap around as well, so use
+    /// [`crate::kernel::continuous::VonMises`], which takes the range width as the period.
+    #[must_use]
+    pub const fn periodic(mut self, periodic: bool) -> Self {
+        self.periodic = periodic;
+        self
+    }
+
+    /// Use the quasi-random initial design for the first trials.
+    ///
+    /// While the design yields points, [`Optimizer::new_trial`] maps them uniformly onto
+    /// the search range instead of sampling from the estimators. Once the design is exhausted,
+    /// the optimizer falls back to the tree-of-Parzen-estimators.
+    ///
+    /// Most designs are infinite, so limit them to the desired number of trials:
+    ///
+    /// ```rust
+    /// use fastrand::Rng;
+    /// use hyperopt::{design::Sobol, kernel::universal::Uniform, Optimizer};
+    ///
+    /// let optimizer = Optimizer::<_, _, i32>::builder(
+    ///     0..=100,
+    ///     Uniform::<_, f64>::with_bounds(0..=100),
+    ///     Rng::with_seed(42),
+    /// )
+    /// .initial_design(Sobol::default().take(10))
+    /// .build();
+    /// ```
+    #[must_use]
+    pub fn initial_design(mut self, design: impl Design + Send + 'static) -> Self {
+        self.initial_design = Some(Box::new(design));
+        self
+    }
+
+    /// Snap the parameter to the quantization grid.
+    ///
+    /// [`Optimizer::new_trial`] then only suggests the grid points, and the densities get integrated
+    /// over the quantization buckets. Parameters fed back with [`Optimizer::feed_back`]
+    /// are snapped too, so that duplicates are detected on the grid.
+    ///
+    /// Preferably, the search range bounds should be on the grid as well.
+    #[must_use]
+    pub fn quantization(mut self, quantization: impl Quantization<P> + Send + 'static) -> Self {
+        self.quantization = Some(Box::new(quantization));
+        self
+    }
+
+    /// Validate the configuration, and build the optimizer.
+    ///
+    /// The configuration cannot be changed afterwards, so that the optimizer fails early
+    /// instead of misbehaving later.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fastrand::Rng;
+    /// use hyperopt::{kernel::universal::Uniform, Error, Optimizer};
+    ///
+    /// let result = Optimizer::<_, _, f64>::builder(
+    ///     0.0..=1.0,
+    ///     Uniform::<f64, f64>::with_bounds(0.0..=1.0),
+    ///     Rng::with_seed(42),
+    /// )
+    /// .cutoff(1.5)
+    /// .try_build();
+    /// assert_eq!(result.unwrap_err(), Error::InvalidCutoff);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::InvalidRange`]: the search range is empty, or has zero width while continuous
+    ///   or periodic
+    /// - [`Error::InvalidCutoff`]: the cutoff is not within `(0, 1]`
+    /// - [`Error::NoCandidates`]: the number of candidates is zero
+    /// - [`Error::InvalidBandwidth`]: the bandwidth multiplier or a bandwidth bound is not positive,
+    ///   or the lower bandwidth bound exceeds the upper one
+    /// - [`Error::ConversionFailed`]: a bandwidth bound or the range width cannot be converted
+    ///   to [`f64`]
+    pub fn try_build(self) -> Result<Optimizer<KInit, P, M, K>, Error>
+    where
+        P: Copy + PartialOrd + Additive + ToPrimitive + Countable,
+    {
+        // A single value is only a valid range for a discrete parameter:
+        let (start, end) = (self.range.start(), self.range.end());
+        let is_single_value = start == end && P::n_values(&self.range).is_some();
+        if !(start < end || (!self.periodic && is_single_value)) {
+            return Err(Error::InvalidRange);
+        }
+        if !(self.cutoff > 0.0 && self.cutoff <= 1.0) {
+            return Err(Error::InvalidCutoff);
+        }
+        if self.n_candidates == 0 {
+            return Err(Error::NoCandidates);
+        }
+        if !(self.bandwidth.is_finite() && self.bandwidth > 0.0) {
+            return Err(Error::InvalidBandwidth);
+        }
+
+        // Resolve the bandwidth bounds in the parameter units:
+        let resolve = |bound: Option<BandwidthBound<P>>| {
+            bound.map(|bound| bound.resolve(&self.range)).transpose()
+        };
+        let (min_bandwidth, max_bandwidth) =
+            (resolve(self.min_bandwidth)?, resolve(self.max_bandwidth)?);
+        if let (Some(min), Some(max)) = (min_bandwidth, max_bandwidth) {
+            if min > max {
+                return Err(Error::InvalidBandwidth);
+            }
+        }
+
+        Ok(Optimizer {
+            cutoff: self.cutoff,
+            n_candidates: self.n_candidates,
+            max_rejections: self.max_rejections,
+            exhaustive_limit: self.exhaustive_limit,
+            scale: Scale {
+                multiplier: self.bandwidth,
+                min: min_bandwidth,
+                max: max_bandwidth,
+            },
+            allow_repeats: self.allow_repeats,
+            reflection: self.reflection,
+            periodic: self.periodic,
+            range: self.range,
+            init_kernel: self.init_kernel,
+            initial_design: self.initial_design,
+            quantization: self.quantization,
+            rng: self.rng,
+            good_trials: Trials::new(),
+            bad_trials: Trials::new(),
+            pending_trials: BTreeMap::new(),
+            next_trial_id: 0,
+            kernel: PhantomData,
+        })
+    }
+
+    /// Validate the configuration, and build the optimizer.
+    ///
+    /// # Panics
+    ///
+    /// This method panics, if the configuration is invalid, see [`OptimizerBuilder::try_build`]
+    /// for the non-panicking version.
+    pub fn build(self) -> Optimizer<KInit, P, M, K>
+    where
+        P: Copy + PartialOrd + Additive + ToPrimitive + Countable,
+    {
+        self.try_build()
+            .unwrap_or_else(|error| panic!("failed to build the optimizer: {error}"))
+    }
+}
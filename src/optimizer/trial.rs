@@ -1,16 +1,179 @@
-use std::{
-    collections::{btree_set::Iter, BTreeSet},
-    fmt::Debug,
-    iter::Copied,
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
 };
+use core::{cmp::Ordering, fmt::Debug, time::Duration};
+
+use crate::traits::ops::TotalOrd;
 
 /// Single trial in the optimizer.
 ///
-/// Note that trials are ordered first by metric, and then by tag.
-#[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
+/// Note that trials are ordered first by metric, and then by parameter, regardless of the metadata.
+/// The ordering is [total][TotalOrd], so that the NaN metrics rank as the worst ones.
+#[derive(Clone, Debug)]
 pub struct Trial<P, M> {
+    /// Sequential number of the trial, starting from zero.
+    ///
+    /// The IDs get allocated in the order the trials are started or fed back. Ignored trials
+    /// do not take the IDs, however, a [started][crate::Optimizer::start_trial] trial reserves
+    /// its ID until it is finished or abandoned.
+    pub id: u64,
+
     pub metric: M,
     pub parameter: P,
+    pub metadata: TrialMetadata,
+}
+
+impl<P, M> Trial<P, M> {
+    /// Evaluation duration, if both timestamps are known.
+    #[must_use]
+    pub fn duration(&self) -> Option<Duration> {
+        self.metadata.duration()
+    }
+}
+
+/// Handle of the pending trial, issued by [`crate::Optimizer::start_trial`].
+///
+/// The handle is consumed by [`crate::Optimizer::finish_trial`]
+/// or [`crate::Optimizer::abandon_trial`].
+#[must_use = "the trial should be finished with `Optimizer::finish_trial` or abandoned"]
+#[derive(Debug)]
+pub struct TrialHandle<P> {
+    pub(crate) id: u64,
+    pub(crate) parameter: P,
+
+    /// Metadata to be retained with the finished trial.
+    pub metadata: TrialMetadata,
+}
+
+impl<P> TrialHandle<P> {
+    /// ID which the finished [`Trial`] will have.
+    #[must_use]
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Parameter value to evaluate.
+    #[must_use]
+    pub const fn parameter(&self) -> P
+    where
+        P: Copy,
+    {
+        self.parameter
+    }
+}
+
+/// Additional information about the trial, which does not affect the optimization.
+///
+/// The timestamps are durations since the [Unix epoch][1], so that they are available
+/// without the standard library.
+///
+/// [1]: https://en.wikipedia.org/wiki/Unix_time
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TrialMetadata {
+    /// When the evaluation has started.
+    pub started_at: Option<Duration>,
+
+    /// When the evaluation has finished.
+    ///
+    /// With the `std` feature, [`crate::Optimizer`] sets it to the current time, when the trial
+    /// is fed back without it.
+    pub finished_at: Option<Duration>,
+
+    /// Arbitrary user attributes, for example, the Git commit hash or the random seed.
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl TrialMetadata {
+    /// Set the start timestamp.
+    #[must_use]
+    pub const fn started_at(mut self, started_at: Duration) -> Self {
+        self.started_at = Some(started_at);
+        self
+    }
+
+    /// Set the finish timestamp.
+    #[must_use]
+    pub const fn finished_at(mut self, finished_at: Duration) -> Self {
+        self.finished_at = Some(finished_at);
+        self
+    }
+
+    /// Set the start timestamp to the current time.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn started_now(self) -> Self {
+        self.started_at(now())
+    }
+
+    /// Add the user attribute.
+    #[must_use]
+    pub fn attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Evaluation duration, if both timestamps are known.
+    ///
+    /// Returns [`None`], if the clock went backwards.
+    #[must_use]
+    pub fn duration(&self) -> Option<Duration> {
+        self.finished_at?.checked_sub(self.started_at?)
+    }
+}
+
+/// Current time since the Unix epoch.
+#[cfg(feature = "std")]
+pub fn now() -> Duration {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+impl<P: TotalOrd, M: TotalOrd> Ord for Trial<P, M> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.metric
+            .total_order(&other.metric)
+            .then_with(|| self.parameter.total_order(&other.parameter))
+    }
+}
+
+impl<P: TotalOrd, M: TotalOrd> PartialOrd for Trial<P, M> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: TotalOrd, M: TotalOrd> Eq for Trial<P, M> {}
+
+impl<P: TotalOrd, M: TotalOrd> PartialEq for Trial<P, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+/// Wrapper, which orders the parameters in the [total order][TotalOrd].
+#[derive(Copy, Clone, Debug)]
+struct Total<T>(T);
+
+impl<T: TotalOrd> Ord for Total<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_order(&other.0)
+    }
+}
+
+impl<T: TotalOrd> PartialOrd for Total<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: TotalOrd> Eq for Total<T> {}
+
+impl<T: TotalOrd> PartialEq for Total<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
 }
 
 /// Ordered collection of trials.
@@ -19,15 +182,19 @@ pub struct Trial<P, M> {
 ///
 /// - Set of trials (a pair of parameter and metric, ordered by metric): that allows tracking of
 ///   the best (worst) trials
-/// - Set of parameters, ordered by parameter itself: that allows to estimate bandwidth for each trial
+/// - Multiset of parameters, ordered by parameter itself: that allows to estimate bandwidth
+///   for each trial
 ///
 /// All this is for the sake of insertion and removal in `O(log n)` time.
 ///
-/// The optimizer **should not** try the same parameter twice.
+/// The optimizer **should not** try the same parameter twice, unless repeats are explicitly allowed.
+/// In that case, the same parameter may appear with different metrics.
 #[derive(Debug)]
 pub struct Trials<P, M> {
     by_metric: BTreeSet<Trial<P, M>>,
-    by_parameter: BTreeSet<P>,
+
+    /// Parameters and their multiplicities.
+    by_parameter: BTreeMap<Total<P>, usize>,
 }
 
 impl<P, M> Trials<P, M> {
@@ -35,55 +202,60 @@ impl<P, M> Trials<P, M> {
     pub const fn new() -> Self {
         Self {
             by_metric: BTreeSet::new(),
-            by_parameter: BTreeSet::new(),
+            by_parameter: BTreeMap::new(),
         }
     }
 
     pub fn len(&self) -> usize {
-        self.by_parameter.len()
+        self.by_metric.len()
     }
 
     pub fn contains(&self, parameter: &P) -> bool
     where
-        P: Ord,
+        P: Copy + TotalOrd,
     {
-        self.by_parameter.contains(parameter)
+        self.by_parameter.contains_key(&Total(*parameter))
     }
 
-    /// Iterate parameters of the trials in ascending order.
-    pub fn iter_parameters(&self) -> Copied<Iter<P>>
+    /// Iterate distinct parameters of the trials in ascending order.
+    pub fn iter_parameters(&self) -> impl Iterator<Item = P> + Clone + '_
     where
         P: Copy,
     {
-        self.by_parameter.iter().copied()
+        self.by_parameter.keys().map(|parameter| parameter.0)
     }
 
     /// Push the trial to the collection.
     ///
-    /// **Repetitive parameters will be ignored.**
+    /// **Repetitive trials will be ignored.**
     ///
     /// # Returns
     ///
     /// [`true`], if the trial was inserted, and [`false`] if it was ignored as repetitive.
     pub fn insert(&mut self, trial: Trial<P, M>) -> bool
     where
-        P: Copy + Ord,
-        M: Ord,
+        P: Copy + TotalOrd,
+        M: TotalOrd,
     {
-        if self.by_parameter.insert(trial.parameter) {
-            assert!(self.by_metric.insert(trial));
-            assert_eq!(self.by_parameter.len(), self.by_metric.len());
+        let parameter = trial.parameter;
+        if self.by_metric.insert(trial) {
+            *self.by_parameter.entry(Total(parameter)).or_default() += 1;
             true
         } else {
             false
         }
     }
 
+    /// Iterate the trials from the best to the worst.
+    pub fn iter(&self) -> impl Iterator<Item = &Trial<P, M>> {
+        self.by_metric.iter()
+    }
+
     /// Retrieve the best trial.
     pub fn best(&self) -> Option<&Trial<P, M>>
     where
-        P: Ord,
-        M: Ord,
+        P: TotalOrd,
+        M: TotalOrd,
     {
         self.by_metric.first()
     }
@@ -91,8 +263,8 @@ impl<P, M> Trials<P, M> {
     /// Retrieve the worst trial.
     pub fn worst(&self) -> Option<&Trial<P, M>>
     where
-        P: Ord,
-        M: Ord,
+        P: TotalOrd,
+        M: TotalOrd,
     {
         self.by_metric.last()
     }
@@ -100,8 +272,8 @@ impl<P, M> Trials<P, M> {
     /// Pop the best trial.
     pub fn pop_best(&mut self) -> Option<Trial<P, M>>
     where
-        P: Ord,
-        M: Ord,
+        P: Copy + TotalOrd,
+        M: TotalOrd,
     {
         let best_trial = self.by_metric.pop_first()?;
         self.remove_parameter(&best_trial.parameter);
@@ -111,73 +283,119 @@ impl<P, M> Trials<P, M> {
     /// Pop the worst trial.
     pub fn pop_worst(&mut self) -> Option<Trial<P, M>>
     where
-        P: Ord,
-        M: Ord,
+        P: Copy + TotalOrd,
+        M: TotalOrd,
     {
         let worst_trial = self.by_metric.pop_last()?;
         self.remove_parameter(&worst_trial.parameter);
         Some(worst_trial)
     }
 
-    /// Remove the parameter and ensure the variants.
+    /// Remove the parameter occurrence and ensure the invariants.
     fn remove_parameter(&mut self, parameter: &P)
     where
-        P: Ord,
-        M: Ord,
+        P: Copy + TotalOrd,
+        M: TotalOrd,
     {
-        assert!(self.by_parameter.remove(parameter));
-        assert_eq!(self.by_parameter.len(), self.by_metric.len());
+        let multiplicity = self
+            .by_parameter
+            .get_mut(&Total(*parameter))
+            .expect("the parameter should be present");
+        *multiplicity -= 1;
+        if *multiplicity == 0 {
+            self.by_parameter.remove(&Total(*parameter));
+        }
+        debug_assert!(self.by_parameter.len() <= self.by_metric.len());
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::optimizer::trial::{Trial, Trials};
+    use alloc::vec::Vec;
+    use core::time::Duration;
+
+    use crate::optimizer::trial::{Trial, TrialMetadata, Trials};
+
+    fn trial<P, M>(metric: M, parameter: P) -> Trial<P, M> {
+        Trial {
+            id: 0,
+            metric,
+            parameter,
+            metadata: TrialMetadata::default(),
+        }
+    }
 
     #[test]
     fn ordering_ok() {
-        assert!(
-            Trial {
-                metric: 42,
-                parameter: 1,
-            } < Trial {
-                metric: 43,
-                parameter: 0,
-            }
-        );
+        assert!(trial(42, 1) < trial(43, 0));
     }
 
     #[test]
     fn trials_ok() {
         let mut trials = Trials::new();
 
-        assert!(trials.insert(Trial {
-            metric: 42,
-            parameter: 1,
-        }));
+        assert!(trials.insert(trial(42, 1)));
         assert_eq!(trials.len(), 1);
         assert_eq!(trials.iter_parameters().collect::<Vec<_>>(), [1]);
 
-        assert!(trials.insert(Trial {
-            metric: 41,
-            parameter: 2,
-        }));
+        assert!(trials.insert(trial(41, 2)));
         assert_eq!(trials.len(), 2);
         assert_eq!(trials.iter_parameters().collect::<Vec<_>>(), [1, 2]);
 
-        assert!(!trials.insert(Trial {
-            metric: 41,
-            parameter: 2,
-        }));
-
-        assert_eq!(
-            trials.pop_worst(),
-            Some(Trial {
-                metric: 42,
-                parameter: 1
-            })
-        );
+        assert!(!trials.insert(trial(41, 2)));
+
+        assert_eq!(trials.pop_worst(), Some(trial(42, 1)));
         assert_eq!(trials.len(), 1);
         assert_eq!(trials.iter_parameters().collect::<Vec<_>>(), [2]);
     }
+
+    #[test]
+    fn nan_metric_is_worst_ok() {
+        let mut trials = Trials::new();
+        assert!(trials.insert(trial(f64::NAN, 1.0)));
+        assert!(trials.insert(trial(f64::INFINITY, 2.0)));
+        assert!(trials.worst().unwrap().metric.is_nan());
+        assert!(trials.contains(&1.0));
+    }
+
+    #[test]
+    fn repeated_parameter_ok() {
+        let mut trials = Trials::new();
+
+        assert!(trials.insert(trial(42, 1)));
+        assert!(trials.insert(trial(43, 1)));
+        assert_eq!(trials.len(), 2);
+        assert_eq!(trials.iter_parameters().collect::<Vec<_>>(), [1]);
+
+        assert!(trials.pop_best().is_some());
+        assert!(trials.contains(&1));
+        assert!(trials.pop_best().is_some());
+        assert!(!trials.contains(&1));
+    }
+
+    #[test]
+    fn metadata_ok() {
+        let metadata = TrialMetadata::default()
+            .started_at(Duration::from_secs(10))
+            .finished_at(Duration::from_secs(12))
+            .attribute("seed", "42");
+        assert_eq!(metadata.duration(), Some(Duration::from_secs(2)));
+        assert_eq!(metadata.attributes["seed"], "42");
+
+        // The clock went backwards:
+        let metadata = metadata.finished_at(Duration::from_secs(9));
+        assert_eq!(metadata.duration(), None);
+    }
+
+    #[test]
+    fn metadata_does_not_affect_ordering_ok() {
+        let mut lhs = trial(42, 1);
+        lhs.id = 1;
+        let rhs = Trial {
+            id: 2,
+            metadata: TrialMetadata::default().attribute("note", "rerun"),
+            ..trial(42, 1)
+        };
+        assert_eq!(lhs, rhs);
+    }
 }
@@ -0,0 +1,271 @@
+//! Quantization of continuous parameters, similar to `quniform` and `qloguniform` in `hyperopt`.
+//!
+//! Some parameters are continuous by nature, but only meaningful at a certain step:
+//! for example, dropout in multiples of `0.05`. [`crate::Optimizer`] snaps such parameters
+//! to the grid, and integrates the densities over each quantization bucket.
+
+use core::{fmt::Debug, ops::Range};
+
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float as _;
+use num_traits::{Bounded, FromPrimitive, ToPrimitive};
+
+use crate::Error;
+
+/// Quantization grid.
+pub trait Quantization<P>: Debug {
+    /// Snap the parameter to the nearest grid point.
+    #[must_use]
+    fn snap(&self, parameter: P) -> P;
+
+    /// Half-open bounds `[start, end)` of the bucket, which the snapped parameter represents.
+    ///
+    /// Every parameter within the bucket snaps to the same grid point,
+    /// so that the neighbouring buckets tile the range without gaps or overlaps.
+    #[must_use]
+    fn bucket(&self, parameter: P) -> Range<P>;
+}
+
+/// Uniform grid of multiples of the step: `floor(x / step + 1/2) * step`.
+///
+/// The ties are rounded up, so that each bucket is `[v - step/2, v + step/2)`.
+#[derive(Copy, Clone, Debug)]
+pub struct Linear<P> {
+    step: P,
+}
+
+impl<P: ToPrimitive> Linear<P> {
+    /// Construct the grid with the specified step.
+    ///
+    /// # Panics
+    ///
+    /// This function panics, if the step is not positive or not finite,
+    /// see [`Linear::try_new`] for the non-panicking version.
+    pub fn new(step: P) -> Self {
+        Self::try_new(step).expect("the quantization step should be positive and finite")
+    }
+
+    /// Construct the grid, just like [`Linear::new`], but return an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidStep`], if the step is not positive or not finite.
+    pub fn try_new(step: P) -> Result<Self, Error> {
+        if is_valid_step(step.to_f64()) {
+            Ok(Self { step })
+        } else {
+            Err(Error::InvalidStep)
+        }
+    }
+}
+
+impl<P> Quantization<P> for Linear<P>
+where
+    P: Copy + Debug + Bounded + ToPrimitive + FromPrimitive,
+{
+    /// Snap the parameter to the nearest grid point, or to the type bound,
+    /// if the grid point does not fit into the type.
+    fn snap(&self, parameter: P) -> P {
+        let step = self.step.to_f64().unwrap();
+        saturating_from_f64((parameter.to_f64().unwrap() / step + 0.5).floor() * step)
+    }
+
+    fn bucket(&self, parameter: P) -> Range<P> {
+        let half_step = self.step.to_f64().unwrap() / 2.0;
+        let parameter = parameter.to_f64().unwrap();
+        edge(parameter - half_step)..edge(parameter + half_step)
+    }
+}
+
+/// Grid of multiples of the step for a parameter, which is optimized in the logarithmic scale.
+///
+/// That is, the parameter is `ln(x)`, and `x` is snapped to `round(x / step) * step`.
+/// The smallest grid point is the step itself, since the logarithm of zero is undefined.
+#[derive(Copy, Clone, Debug)]
+pub struct Logarithmic {
+    step: f64,
+}
+
+impl Logarithmic {
+    /// Construct the grid with the specified step in the original (non-logarithmic) scale.
+    ///
+    /// # Panics
+    ///
+    /// This function panics, if the step is not positive or not finite,
+    /// see [`Logarithmic::try_new`] for the non-panicking version.
+    #[must_use]
+    pub fn new(step: f64) -> Self {
+        Self::try_new(step).expect("the quantization step should be positive and finite")
+    }
+
+    /// Construct the grid, just like [`Logarithmic::new`], but return an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidStep`], if the step is not positive or not finite.
+    pub fn try_new(step: f64) -> Result<Self, Error> {
+        if is_valid_step(Some(step)) {
+            Ok(Self { step })
+        } else {
+            Err(Error::InvalidStep)
+        }
+    }
+}
+
+/// Convert the bucket edge into the parameter, saturating at the type bounds.
+///
+/// The conversion truncates the integer parameters, whereas the integers within `[start, end)`
+/// are exactly those within `[ceil(start), ceil(end))`, hence rounding the edge up instead.
+#[allow(clippy::float_cmp)]
+fn edge<P: Bounded + ToPrimitive + FromPrimitive>(value: f64) -> P {
+    let parameter: P = saturating_from_f64(value);
+    if value.fract() > 0.0 && parameter.to_f64() == Some(value.trunc()) {
+        saturating_from_f64(value.ceil())
+    } else {
+        parameter
+    }
+}
+
+/// Convert the number into the parameter, saturating at the type bounds,
+/// for example, when a grid point or a bucket edge falls out of the unsigned type.
+fn saturating_from_f64<P: Bounded + FromPrimitive>(value: f64) -> P {
+    P::from_f64(value).unwrap_or_else(|| {
+        if value < 0.0 {
+            P::min_value()
+        } else {
+            P::max_value()
+        }
+    })
+}
+
+/// Check that the step is positive and finite, so that snapping never divides by zero.
+fn is_valid_step(step: Option<f64>) -> bool {
+    step.is_some_and(|step| step.is_finite() && step > 0.0)
+}
+
+impl<P> Quantization<P> for Logarithmic
+where
+    P: Copy + Bounded + ToPrimitive + FromPrimitive,
+{
+    fn snap(&self, parameter: P) -> P {
+        let multiple = (parameter.to_f64().unwrap().exp() / self.step)
+            .round()
+            .max(1.0);
+        saturating_from_f64((multiple * self.step).ln())
+    }
+
+    /// The smallest grid point also represents everything below it, down to zero,
+    /// hence its bucket starts at the negative infinity.
+    fn bucket(&self, parameter: P) -> Range<P> {
+        let multiple = (parameter.to_f64().unwrap().exp() / self.step)
+            .round()
+            .max(1.0);
+        let start = if multiple > 1.0 {
+            ((multiple - 0.5) * self.step).ln()
+        } else {
+            f64::NEG_INFINITY
+        };
+        saturating_from_f64(start)..saturating_from_f64(((multiple + 0.5) * self.step).ln())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn linear_continuous_ok() {
+        let quantization = Linear::new(0.05);
+        assert_abs_diff_eq!(quantization.snap(0.123), 0.1);
+        assert_abs_diff_eq!(quantization.snap(0.126), 0.15);
+
+        let bucket = quantization.bucket(0.1);
+        assert_abs_diff_eq!(bucket.start, 0.075);
+        assert_abs_diff_eq!(bucket.end, 0.125);
+    }
+
+    #[test]
+    fn linear_discrete_ok() {
+        let quantization = Linear::new(32);
+        assert_eq!(quantization.snap(47), 32);
+        assert_eq!(quantization.snap(49), 64);
+        assert_eq!(quantization.snap(-49), -64);
+        assert_eq!(quantization.snap(48), 64);
+        assert_eq!(quantization.snap(-48), -32);
+        assert_eq!(quantization.bucket(64), 48..80);
+        assert_eq!(Linear::new(5).bucket(10), 8..13);
+        assert_eq!(Linear::new(5).bucket(-10), -12..-7);
+    }
+
+    #[test]
+    fn linear_type_bounds_ok() {
+        // The grid point `256` and the bucket edge `272` do not fit into `u8`:
+        let quantization = Linear::new(32_u8);
+        assert_eq!(quantization.snap(255), u8::MAX);
+        assert_eq!(quantization.snap(240), u8::MAX);
+        assert_eq!(quantization.bucket(224), 208..240);
+        assert_eq!(quantization.bucket(255), 239..u8::MAX);
+
+        // The bucket edge `-5` does not fit into `u32`:
+        let quantization = Linear::new(10_u32);
+        assert_eq!(quantization.snap(0), 0);
+        assert_eq!(quantization.bucket(0), 0..5);
+        assert_eq!(quantization.snap(u32::MAX), u32::MAX);
+        assert_eq!(quantization.bucket(u32::MAX).end, u32::MAX);
+    }
+
+    #[test]
+    fn linear_buckets_tile_ok() {
+        let quantization = Linear::new(5);
+        for parameter in -20..=20 {
+            let snapped = quantization.snap(parameter);
+            assert!(quantization.bucket(snapped).contains(&parameter));
+            assert_eq!(
+                quantization.bucket(snapped).end,
+                quantization.bucket(snapped + 5).start
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_step_ok() {
+        assert_eq!(Linear::try_new(0).unwrap_err(), Error::InvalidStep);
+        assert_eq!(Linear::try_new(-0.5).unwrap_err(), Error::InvalidStep);
+        assert_eq!(Linear::try_new(f64::NAN).unwrap_err(), Error::InvalidStep);
+        assert_eq!(
+            Logarithmic::try_new(f64::INFINITY).unwrap_err(),
+            Error::InvalidStep
+        );
+        assert!(Logarithmic::try_new(0.5).is_ok());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn logarithmic_ok() {
+        let quantization = Logarithmic::new(0.5);
+        assert_abs_diff_eq!(quantization.snap(1.3_f64.ln()).exp(), 1.5);
+        assert_abs_diff_eq!(quantization.snap(0.01_f64.ln()).exp(), 0.5);
+
+        let bucket = quantization.bucket(1.5_f64.ln());
+        assert_abs_diff_eq!(bucket.start.exp(), 1.25);
+        assert_abs_diff_eq!(bucket.end.exp(), 1.75);
+
+        let bucket = quantization.bucket(0.5_f64.ln());
+        assert_eq!(bucket.start, f64::NEG_INFINITY);
+        assert_abs_diff_eq!(bucket.end.exp(), 0.75);
+    }
+
+    #[test]
+    fn logarithmic_buckets_tile_ok() {
+        let quantization = Logarithmic::new(0.5);
+        for value in [0.01, 0.3, 0.74, 0.76, 1.3, 2.0, 10.1] {
+            let parameter = f64::ln(value);
+            let bucket = quantization.bucket(quantization.snap(parameter));
+            assert!(bucket.contains(&parameter), "{value}");
+            let next = quantization.bucket((bucket.end.exp() + 0.25).ln());
+            assert_abs_diff_eq!(bucket.end, next.start, epsilon = 1e-12);
+        }
+    }
+}
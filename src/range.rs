@@ -1,4 +1,4 @@
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
 
 /// [`Copy`] implementation for ranges, since they do not want to include it into the
 /// standard library 😕
@@ -0,0 +1,22 @@
+//! Multi-parameter search spaces.
+
+use fastrand::Rng;
+
+/// Search space of several parameters, normally implemented with `#[derive(SearchSpace)]`.
+///
+/// Every parameter gets its own [`crate::Optimizer`], and all of them are fed back with
+/// the same metric – just like the original `hyperopt` treats the parameters independently.
+///
+/// The derive macro requires the `derive` feature.
+pub trait SearchSpace: Sized {
+    /// Optimizer of the entire space, suggesting and accepting [`Self`].
+    type Optimizer<M>;
+
+    /// Construct the optimizer for the space.
+    ///
+    /// # Panics
+    ///
+    /// The derived implementation panics, if a search range is empty,
+    /// see [`crate::OptimizerBuilder::build`].
+    fn optimizer<M>(rng: Rng) -> Self::Optimizer<M>;
+}
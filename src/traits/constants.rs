@@ -4,7 +4,11 @@
 //! Some _are_ available, but only through [`num_traits::Float`], which is not implemented
 //! for certain types. All in all, it was easier to go this way.
 
-#![allow(clippy::excessive_precision, clippy::unreadable_literal)]
+#![allow(
+    clippy::approx_constant,
+    clippy::excessive_precision,
+    clippy::unreadable_literal
+)]
 
 macro_rules! define_trait {
     ($trait_:ident, $ident:ident, $value:literal, $comment:literal) => {
@@ -26,6 +30,7 @@ macro_rules! define_trait {
     };
 }
 
+#[cfg(feature = "ordered-float")]
 macro_rules! impl_for_ordered_float {
     ($trait_:ident, $ident:ident) => {
         impl $trait_ for ordered_float::NotNan<f32> {
@@ -46,6 +51,18 @@ macro_rules! impl_for_ordered_float {
     };
 }
 
+define_trait!(
+    ConstSqrt2,
+    SQRT_2,
+    1.4142135623730950488016887242096980785696718753769480731766797380,
+    "√2"
+);
+define_trait!(
+    ConstFrac1Sqrt2,
+    FRAC_1_SQRT_2,
+    0.7071067811865475244008443621048490392848359376884740365883398690,
+    "1 / √2"
+);
 define_trait!(
     ConstSqrt3,
     SQRT_3,
@@ -58,6 +75,18 @@ define_trait!(
     2.2360679774997896964091736687312762354406183596115257242708972454,
     "√5"
 );
+define_trait!(
+    ConstSqrt6,
+    SQRT_6,
+    2.4494897427831780981972840747058913919659474806566701284326925673,
+    "√6"
+);
+define_trait!(
+    ConstSqrt7,
+    SQRT_7,
+    2.6457513110645905905016157536392604257102591830824501803683344592,
+    "√7"
+);
 define_trait!(
     ConstDoubleSqrt3,
     DOUBLE_SQRT_3,
@@ -70,5 +99,43 @@ define_trait!(
     0.3989422804014326779399460599343818684758586311649346576659258296,
     "1 / √(2π)"
 );
+define_trait!(
+    ConstLnFrac1SqrtTau,
+    LN_FRAC_1_SQRT_TAU,
+    -0.9189385332046727417803297364056176398613974736377834128171515405,
+    "ln(1 / √(2π))"
+);
+define_trait!(
+    ConstFrac1Pi,
+    FRAC_1_PI,
+    0.3183098861837906715377675267450287240689192914809128974953346881,
+    "1 / π"
+);
+define_trait!(
+    ConstFracPiSqrt3,
+    FRAC_PI_SQRT_3,
+    1.8137993642342178505940782576421557322840662480927405755698849354,
+    "π / √3"
+);
+define_trait!(
+    ConstCosineScale,
+    COSINE_SCALE,
+    2.2976031174871966792266282755011806528704284612352833115742288661,
+    "π / √(π² - 8), the half-width of the standardized cosine kernel"
+);
+define_trait!(ConstThree, THREE, 3.0, "`3.0`");
+define_trait!(
+    ConstThirtyFiveThirtySeconds,
+    THIRTY_FIVE_THIRTY_SECONDS,
+    1.09375,
+    "`35 / 32`"
+);
+define_trait!(
+    ConstFifteenSixteenths,
+    FIFTEEN_SIXTEENTHS,
+    0.9375,
+    "`15 / 16`"
+);
 define_trait!(ConstThreeQuarters, THREE_QUARTERS, 0.75, "`0.75`");
 define_trait!(ConstOneHalf, ONE_HALF, 0.5, "`0.5`");
+define_trait!(ConstOneQuarter, ONE_QUARTER, 0.25, "`0.25`");
@@ -1,3 +1,7 @@
+//! Operations, which the kernels and estimators are generic over.
+
+use core::{cmp::Ordering, iter, ops::RangeInclusive};
+
 /// Self-negation
 pub trait Neg: core::ops::Neg<Output = Self> {}
 
@@ -25,8 +29,14 @@ impl<T> Multiplicative for T where
 {
 }
 
+/// Remainder of the division.
+pub trait Rem: core::ops::Rem<Output = Self> + Sized {}
+
+impl<T> Rem for T where T: core::ops::Rem<Output = Self> {}
+
 pub trait MulAdd {
     /// Perform fused `(self * a) + b`.
+    #[must_use]
     fn mul_add(self, a: Self, b: Self) -> Self;
 }
 
@@ -44,16 +54,281 @@ pub trait Arithmetic: Additive + Multiplicative {}
 
 impl<T> Arithmetic for T where T: Additive + Multiplicative {}
 
+/// Exponentiation of the densities.
+///
+/// It is implemented for the plain and `ordered-float` floats. Since `ordered_float::NotNan`
+/// does not implement [`num_traits::Float`], there is no blanket implementation.
 pub trait Exp {
     /// Raise `e` to the power of `self`.
+    #[must_use]
     fn exp(self) -> Self;
 }
 
-impl<T> Exp for T
-where
-    T: num_traits::Float,
-{
-    fn exp(self) -> Self {
-        num_traits::Float::exp(self)
+pub trait Ln {
+    /// Calculate the natural logarithm of `self`.
+    #[must_use]
+    fn ln(self) -> Self;
+}
+
+/// [Total order][1], which also covers the floating-point numbers.
+///
+/// This allows the plain [`f32`] and [`f64`] for both parameters and metrics.
+/// The floats are ordered by their [`f64::total_cmp`], except that any NaN is greater than
+/// any number, so that the NaN metrics rank as the worst ones.
+///
+/// [1]: https://en.wikipedia.org/wiki/Total_order
+pub trait TotalOrd: PartialOrd {
+    /// Compare the values in the total order.
+    #[must_use]
+    fn total_order(&self, other: &Self) -> Ordering;
+
+    /// Check whether the value is NaN. Non-float types are never NaN.
+    #[must_use]
+    fn is_nan(&self) -> bool {
+        false
+    }
+
+    /// Get the maximum of the values in the total order.
+    #[must_use]
+    fn total_max(self, other: Self) -> Self
+    where
+        Self: Sized,
+    {
+        if other.total_order(&self).is_lt() {
+            self
+        } else {
+            other
+        }
+    }
+}
+
+macro_rules! impl_total_ord {
+    ($($type:ty),+) => {
+        $(
+            impl TotalOrd for $type {
+                fn total_order(&self, other: &Self) -> Ordering {
+                    Ord::cmp(self, other)
+                }
+            }
+        )+
+    };
+}
+
+impl_total_ord!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_total_ord_float {
+    ($type:ty) => {
+        impl TotalOrd for $type {
+            fn total_order(&self, other: &Self) -> Ordering {
+                match (self.is_nan(), other.is_nan()) {
+                    (false, false) => self.total_cmp(other),
+                    (self_is_nan, other_is_nan) => self_is_nan.cmp(&other_is_nan),
+                }
+            }
+
+            fn is_nan(&self) -> bool {
+                <$type>::is_nan(*self)
+            }
+        }
+    };
+}
+
+impl_total_ord_float!(f32);
+impl_total_ord_float!(f64);
+
+/// [`ordered_float::OrderedFloat`] already puts NaN above any number, and
+/// [`ordered_float::NotNan`] is never NaN.
+#[cfg(feature = "ordered-float")]
+macro_rules! impl_total_ord_ordered_float {
+    ($type:ty) => {
+        impl TotalOrd for ordered_float::OrderedFloat<$type> {
+            fn total_order(&self, other: &Self) -> Ordering {
+                Ord::cmp(self, other)
+            }
+
+            fn is_nan(&self) -> bool {
+                self.0.is_nan()
+            }
+        }
+
+        impl TotalOrd for ordered_float::NotNan<$type> {
+            fn total_order(&self, other: &Self) -> Ordering {
+                Ord::cmp(self, other)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "ordered-float")]
+impl_total_ord_ordered_float!(f32);
+
+#[cfg(feature = "ordered-float")]
+impl_total_ord_ordered_float!(f64);
+
+/// Parameter type, whose values in a range may be enumerated.
+///
+/// [`crate::Optimizer`] evaluates the acquisition function on every value of a small discrete
+/// search range instead of sampling the candidates. By default, the type is considered continuous,
+/// and never gets enumerated.
+pub trait Countable: Sized {
+    /// Count the values in the range.
+    ///
+    /// Returns [`None`] for a continuous type, or if the count does not fit into [`usize`].
+    #[must_use]
+    fn n_values(range: &RangeInclusive<Self>) -> Option<usize> {
+        let _ = range;
+        None
+    }
+
+    /// Iterate over the values in the range in the ascending order.
+    #[must_use]
+    fn values(range: RangeInclusive<Self>) -> impl Iterator<Item = Self> {
+        let _ = range;
+        iter::empty()
+    }
+
+    /// Get the previous value.
+    ///
+    /// Returns [`None`] for a continuous type, or at the type minimum.
+    #[must_use]
+    fn predecessor(&self) -> Option<Self> {
+        None
+    }
+
+    /// Get the next value.
+    ///
+    /// Returns [`None`] for a continuous type, or at the type maximum.
+    #[must_use]
+    fn successor(&self) -> Option<Self> {
+        None
+    }
+}
+
+macro_rules! impl_countable {
+    ($($type:ty),+) => {
+        $(
+            impl Countable for $type {
+                fn n_values(range: &RangeInclusive<Self>) -> Option<usize> {
+                    if range.is_empty() {
+                        return Some(0);
+                    }
+                    usize::try_from(range.end().abs_diff(*range.start())).ok()?.checked_add(1)
+                }
+
+                fn values(range: RangeInclusive<Self>) -> impl Iterator<Item = Self> {
+                    range
+                }
+
+                fn predecessor(&self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn successor(&self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+            }
+        )+
+    };
+}
+
+impl_countable!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl Countable for f32 {}
+
+impl Countable for f64 {}
+
+#[cfg(feature = "ordered-float")]
+impl<T> Countable for ordered_float::OrderedFloat<T> {}
+
+#[cfg(feature = "ordered-float")]
+impl<T> Countable for ordered_float::NotNan<T> {}
+
+macro_rules! impl_float {
+    ($type:ty) => {
+        impl Exp for $type {
+            fn exp(self) -> Self {
+                num_traits::Float::exp(self)
+            }
+        }
+
+        impl Ln for $type {
+            fn ln(self) -> Self {
+                num_traits::Float::ln(self)
+            }
+        }
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
+
+#[cfg(feature = "ordered-float")]
+impl_float!(ordered_float::OrderedFloat<f32>);
+
+#[cfg(feature = "ordered-float")]
+impl_float!(ordered_float::OrderedFloat<f64>);
+
+/// [`ordered_float::NotNan`] does not implement [`num_traits::Float`], but its exponent and
+/// logarithm are well-defined for the densities, which are never negative.
+#[cfg(feature = "ordered-float")]
+macro_rules! impl_not_nan {
+    ($type:ty) => {
+        impl Exp for ordered_float::NotNan<$type> {
+            fn exp(self) -> Self {
+                Self::new(self.into_inner().exp()).expect("exponent should not be NaN")
+            }
+        }
+
+        impl Ln for ordered_float::NotNan<$type> {
+            fn ln(self) -> Self {
+                Self::new(self.into_inner().ln()).expect("logarithm should not be NaN")
+            }
+        }
+    };
+}
+
+#[cfg(feature = "ordered-float")]
+impl_not_nan!(f32);
+
+#[cfg(feature = "ordered-float")]
+impl_not_nan!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_order_ok() {
+        assert!(1.0.total_order(&2.0).is_lt());
+        assert!((-0.0).total_order(&0.0).is_lt());
+        assert!(f64::INFINITY.total_order(&f64::NAN).is_lt());
+        assert!((-f64::NAN).total_order(&f64::INFINITY).is_gt());
+        assert!(f64::NAN.total_order(&-f64::NAN).is_eq());
+        assert!(1.total_order(&2).is_lt());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn total_max_ok() {
+        assert_eq!(1.0.total_max(2.0), 2.0);
+        assert!(1.0.total_max(f64::NAN).is_nan());
+        assert_eq!(2.total_max(1), 2);
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn countable_ok() {
+        assert_eq!(i32::n_values(&(-1..=1)), Some(3));
+        assert_eq!(i32::n_values(&(1..=0)), Some(0));
+        assert_eq!(u64::n_values(&(0..=u64::MAX)), None);
+        assert_eq!(
+            i8::values(-1..=1).collect::<alloc::vec::Vec<_>>(),
+            [-1, 0, 1]
+        );
+        assert_eq!(f64::n_values(&(0.0..=1.0)), None);
+        assert_eq!(0_u8.predecessor(), None);
+        assert_eq!(0_u8.successor(), Some(1));
+        assert_eq!(i8::MAX.successor(), None);
+        assert_eq!(1.0_f64.successor(), None);
     }
 }